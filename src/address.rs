@@ -1,12 +1,15 @@
 //---------------------------------------------------------------------------------------------------- Use
-use regex::Regex;
+use crate::regexes::Patterns;
+use crate::hit::Hit;
 use rand::Rng;
 use monero::{
-	Network,
 	PrivateKey,
 	KeyPair,
 	Address, PublicKey,
 };
+use crate::network::Network;
+use crate::encode::SPEND_CHARS;
+use crate::checkpoint::{Position,Positions};
 use curve25519_dalek::scalar::Scalar;
 use curve25519_dalek::edwards::EdwardsPoint;
 use curve25519_dalek::constants::ED25519_BASEPOINT_TABLE;
@@ -19,32 +22,127 @@ use std::sync::atomic::{
 	AtomicU64,
 };
 //--------------------------------------------------------------------------------------------------- Constants.
-// Mainnet Monero Network.
-const NETWORK_BYTE: u8 = 18;
-// Mainnet Monero Network.
-const NETWORK_ARRAY: &[u8] = &[18];
 // How many `EdwardsPoint`'s to
 // compress in batch in one go.
 const BATCH_SIZE: usize = 10_000;
 const BATCH_SIZE_U64: u64 = BATCH_SIZE as u64;
+// Subaddresses need a hash + scalar multiplication
+// per try, so the batches are smaller.
+const SUBADDRESS_BATCH_SIZE: usize = 1_000;
+// `Hs("SubAddr\0" || a || major || minor)` domain separator.
+const SUBADDRESS_PREFIX: &[u8; 8] = b"SubAddr\0";
+// How many minor indices an account has.
+const SUBADDRESS_INDICES: u64 = u32::MAX as u64 + 1;
+// Length of a raw standard/integrated address in bytes.
+const STANDARD_LEN: usize = 69;
+const INTEGRATED_LEN: usize = 77;
 
 //---------------------------------------------------------------------------------------------------- Spawn worker threads.
 #[inline(always)]
+#[allow(clippy::too_many_arguments)]
 pub fn spawn_workers(
 	threads: usize,
-	to_main: &std::sync::mpsc::Sender::<(String, String, String)>,
+	to_main: &std::sync::mpsc::Sender::<Hit>,
 	iter: &Arc<AtomicU64>,
 	die: &Arc<AtomicBool>,
-	regex: &Regex,
-	split_key: Option<EdwardsPoint>
+	positions: &Positions,
+	patterns: &Patterns,
+	split_key: Option<EdwardsPoint>,
+	random_view: bool,
+	network: Network,
+) {
+	for thread in 0..threads {
+		let to_main = to_main.clone();
+		let iter    = iter.clone();
+		let die     = die.clone();
+		let positions = positions.clone();
+		let patterns = patterns.clone();
+
+		std::thread::spawn(move || calculate(to_main, iter, die, positions, thread, patterns, split_key, random_view, network));
+	}
+}
+
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+/// Same as [`spawn_workers()`] but for subaddresses of an existing wallet.
+///
+/// `view` is the wallet's private view key, `spend` is its public spend key.
+pub fn spawn_subaddress_workers(
+	threads: usize,
+	to_main: &std::sync::mpsc::Sender::<Hit>,
+	iter: &Arc<AtomicU64>,
+	die: &Arc<AtomicBool>,
+	positions: &Positions,
+	patterns: &Patterns,
+	view: PrivateKey,
+	spend: EdwardsPoint,
+	major: u32,
+	random_index: bool,
+	network: Network,
 ) {
-	for _ in 0..threads {
+	for thread in 0..threads {
 		let to_main = to_main.clone();
 		let iter    = iter.clone();
 		let die     = die.clone();
-		let regex   = regex.clone();
+		let positions = positions.clone();
+		let patterns = patterns.clone();
 
-		std::thread::spawn(move || calculate(to_main, iter, die, regex, split_key));
+		std::thread::spawn(move || calculate_subaddress(to_main, iter, die, positions, thread, patterns, view, spend, major, threads, random_index, network));
+	}
+}
+
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+/// Same as [`spawn_workers()`] but for the end of the address.
+///
+/// `spend` stays fixed and the view key is searched instead.
+/// `patterns` are matched against the last `18` characters of the address.
+pub fn spawn_suffix_workers(
+	threads: usize,
+	to_main: &std::sync::mpsc::Sender::<Hit>,
+	iter: &Arc<AtomicU64>,
+	die: &Arc<AtomicBool>,
+	positions: &Positions,
+	patterns: &Patterns,
+	spend: PrivateKey,
+	network: Network,
+) {
+	for thread in 0..threads {
+		let to_main = to_main.clone();
+		let iter    = iter.clone();
+		let die     = die.clone();
+		let positions = positions.clone();
+		let patterns = patterns.clone();
+
+		std::thread::spawn(move || calculate_suffix(to_main, iter, die, positions, thread, patterns, spend, network));
+	}
+}
+
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+/// Same as [`spawn_workers()`] but for the payment ID of an integrated address.
+///
+/// `spend` and `view` are the public keys of the wallet, they stay fixed.
+/// `patterns` are matched against the last `18` characters of the address.
+pub fn spawn_integrated_workers(
+	threads: usize,
+	to_main: &std::sync::mpsc::Sender::<Hit>,
+	iter: &Arc<AtomicU64>,
+	die: &Arc<AtomicBool>,
+	positions: &Positions,
+	patterns: &Patterns,
+	spend: PublicKey,
+	view: PublicKey,
+	network: Network,
+) {
+	for thread in 0..threads {
+		let to_main = to_main.clone();
+		let iter    = iter.clone();
+		let die     = die.clone();
+		let positions = positions.clone();
+		let patterns = patterns.clone();
+
+		std::thread::spawn(move || calculate_integrated(to_main, iter, die, positions, thread, patterns, spend, view, network));
 	}
 }
 
@@ -56,84 +154,142 @@ fn rand_scalar() -> Scalar {
 	Scalar::from_bytes_mod_order_wide(&x)
 }
 
-fn rand_priv() -> PrivateKey {
+/// A new random private key.
+pub fn rand_priv() -> PrivateKey {
 	PrivateKey { scalar: rand_scalar() }
 }
+
+//---------------------------------------------------------------------------------------------------- View key.
+/// Derive the private view key from the private spend key.
+///
+/// This is `Keccak256(spend) mod l`, the same derivation
+/// `monero-wallet-cli --generate-from-spend-key` and the
+/// 25-word seed use, so restoring gives the same address.
+pub fn view_from_spend(spend: &PrivateKey) -> PrivateKey {
+	monero::cryptonote::hash::Hash::hash_to_scalar(spend.as_bytes())
+}
+
+// Either derive the view key from `spend`, or generate a random one.
+fn view_key(spend: &PrivateKey, random_view: bool) -> PrivateKey {
+	match random_view {
+		true  => rand_priv(),
+		false => view_from_spend(spend),
+	}
+}
+//---------------------------------------------------------------------------------------------------- Checkpoint positions.
+// Where this thread left off, if resuming.
+fn resume(positions: &Positions, thread: usize) -> Option<Position> {
+	positions.get(thread).and_then(|p| *p.lock().unwrap())
+}
+
+// The resumed seed + tries, or a new random seed.
+fn seed_and_tries(positions: &Positions, thread: usize) -> (Scalar, u64) {
+	match resume(positions, thread) {
+		Some(p) => (Scalar::from_bytes_mod_order(p.seed), p.offset),
+		None    => (rand_scalar(), 0),
+	}
+}
+
+// Save where this thread is, its next try is `seed + offset`.
+fn save(positions: &Positions, thread: usize, seed: [u8; 32], offset: u64) {
+	if let Some(p) = positions.get(thread) {
+		*p.lock().unwrap() = Some(Position { seed, offset });
+	}
+}
+
 //---------------------------------------------------------------------------------------------------- Split key calculations.
+/// A new private split key part + its public part (with a proof of possession) to give out.
 pub fn calculate_part_split_key() -> (String, String) {
 	let private = rand_priv();
-	let public = PublicKey::from_private_key(&private);
-	(private.to_string(), public.to_string())
+	(private.to_string(), crate::split::part(&private))
 }
 
-pub fn join_split_key(key_1: PrivateKey, key_2: PrivateKey) -> (String, String, String) {
-	let spend = key_1 + key_2;
-	let view = rand_priv();
+/// Sum the private parts of every contributor into the spend key.
+pub fn join_split_key(keys: &[PrivateKey], random_view: bool, network: Network) -> (String, String, String) {
+	let spend = PrivateKey { scalar: keys.iter().map(|key| key.scalar).sum() };
+	let view = view_key(&spend, random_view);
 	let pair = KeyPair { view, spend };
-	let address = Address::from_keypair(Network::Mainnet, &pair);
+	let address = Address::from_keypair(network.monero(), &pair);
 	(address.to_string(), spend.to_string(), view.to_string())
 }
 
 //---------------------------------------------------------------------------------------------------- Calculate the address.
+/// The loop of 1 [`spawn_workers()`] thread, it returns once `die` is set.
 #[inline(always)]
-fn calculate(
-	to_main: std::sync::mpsc::Sender::<(String, String, String)>,
+#[allow(clippy::too_many_arguments)]
+pub fn calculate(
+	to_main: std::sync::mpsc::Sender::<Hit>,
 	iter: Arc<AtomicU64>,
 	die: Arc<AtomicBool>,
-	regex: Regex,
+	positions: Positions,
+	thread: usize,
+	patterns: Patterns,
 	split_key: Option<EdwardsPoint>,
+	random_view: bool,
+	network: Network,
 ) {
-	// Seed.
-	let seed = rand_scalar();
+	// Network byte.
+	let network_byte = network.byte();
+
+	// How many `11` character blocks the patterns need.
+	let blocks = patterns.blocks();
+
+	// Seed + thread local iteration count,
+	// either resumed from a checkpoint or random.
+	let (mut seed, mut tries) = seed_and_tries(&positions, thread);
 
 	// Base Point.
-	let mut point = &seed * &ED25519_BASEPOINT_TABLE;
-	if let Some(split_key) = split_key {
-		point += split_key;
-	}
+	let base = |seed: &Scalar| match split_key {
+		Some(split_key) => seed * &ED25519_BASEPOINT_TABLE + split_key,
+		None            => seed * &ED25519_BASEPOINT_TABLE,
+	};
+	let mut point = base(&(seed + Scalar::from(tries)));
 
 	// Offset.
 	let offset = &Scalar::from(1_u8) * &ED25519_BASEPOINT_TABLE;
 
-	// Thread local iteration count.
-	let mut tries: u64 = 0;
-
 	loop {
 		// Batch compress the `EdwardsPoint`'s.
 		let mut eds: Vec<EdwardsPoint> = Vec::with_capacity(BATCH_SIZE);
-		for i in 0..BATCH_SIZE {
+		for _ in 0..BATCH_SIZE {
 			eds.push(point);
 			point += offset;
 		}
 		let y_points = EdwardsPoint::batch_compress_edwards(&mut eds);
 
+		// How many keys of this batch were actually tried.
+		let mut checked = 0_u64;
+
 		// Iterate over `CompressedEdwardsY` (public key)
 		for y in y_points {
-			// Calculate 1st `11` characters of Monero address.
-			let mut bytes = [0_u8; 11];
-			bytes[0] = NETWORK_BYTE;
-			bytes[1..].copy_from_slice(&y.as_bytes()[0..10]);
+			checked += 1;
 
-			let addr = &crate::encode::encode_11(&bytes);
+			// Calculate 1st `11` (or up to `44`) characters of Monero address.
+			let mut bytes = [0_u8; 33];
+			bytes[0] = network_byte;
+			bytes[1..].copy_from_slice(y.as_bytes());
+
+			let mut addr = [0_u8; SPEND_CHARS];
+			let len = crate::encode::encode_blocks(&bytes, blocks, &mut addr);
 
 			// Check for regex match.
 			// SAFETY:
 			// The input is known UTF-8 compatible bytes.
-			if regex.is_match(unsafe { &std::str::from_utf8_unchecked(&addr[..]) }) {
-				// If found, signal to other threads.
-				die.store(true, std::sync::atomic::Ordering::SeqCst);
-
+			if let Some(pattern) = patterns.find(unsafe { std::str::from_utf8_unchecked(&addr[..len]) }) {
 				// Create Private Spend/View Keypair.
 				let spend = PrivateKey { scalar: seed + Scalar::from(tries) };
-				let view = rand_priv();
+				let view = view_key(&spend, random_view);
 				let pair = KeyPair { view, spend };
 
-				let address = Address::from_keypair(Network::Mainnet, &pair);
+				let address = Address::from_keypair(network.monero(), &pair);
 
-				// Send to `GUI`.
-				to_main.send((address.to_string(), spend.to_string(), view.to_string()));
+				// Send to `main`, it decides when to stop.
+				let _ = to_main.send(Hit::Wallet { address: address.to_string(), spend, view, pattern });
 
-				// Exit.
+				// Reseed, the rest of this batch belongs to the old seed.
+				seed  = rand_scalar();
+				point = base(&seed);
+				tries = 0;
 				break
 			}
 
@@ -141,20 +297,719 @@ fn calculate(
 			tries += 1;
 		}
 
+		// Save where this thread is.
+		save(&positions, thread, seed.to_bytes(), tries);
+
 		// Exit if `die` signal is set.
 		if die.load(std::sync::atomic::Ordering::SeqCst) == true {
 			break
 		}
 
+		// Increment `iteration`.
+		iter.fetch_add(checked, std::sync::atomic::Ordering::SeqCst);
+	}
+}
+
+//---------------------------------------------------------------------------------------------------- Calculate the subaddress.
+// Write the `minor` index into the
+// hash input and return `m = Hs(data)`.
+#[inline(always)]
+fn subaddress_scalar(data: &mut [u8; 48], minor: u32) -> Scalar {
+	data[44..].copy_from_slice(&minor.to_le_bytes());
+	monero::cryptonote::hash::Hash::hash_to_scalar(&data[..]).scalar
+}
+
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+fn calculate_subaddress(
+	to_main: std::sync::mpsc::Sender::<Hit>,
+	iter: Arc<AtomicU64>,
+	die: Arc<AtomicBool>,
+	positions: Positions,
+	thread: usize,
+	patterns: Patterns,
+	view: PrivateKey,
+	spend: EdwardsPoint,
+	major: u32,
+	threads: usize,
+	random_index: bool,
+	network: Network,
+) {
+	// Network byte.
+	let network_byte = network.subaddress_byte();
+
+	// How many `11` character blocks the patterns need.
+	let blocks = patterns.blocks();
+
+	// Hash input: "SubAddr\0" || a || major || minor
+	let mut data = [0_u8; 48];
+	data[..8].copy_from_slice(SUBADDRESS_PREFIX);
+	data[8..40].copy_from_slice(view.as_bytes());
+	data[40..44].copy_from_slice(&major.to_le_bytes());
+
+	// Each thread walks every `threads`'th minor index,
+	// either from the start or from a random index,
+	// and returns once it walked all of them.
+	// Random indices wrap around, so they end `2^32` after the start.
+	let step = threads as u64;
+	let (mut minor, end): (u64, u64) = match (resume(&positions, thread), random_index) {
+		(Some(p), _)  => (p.offset, u64::from_le_bytes(p.seed[..8].try_into().expect("8 bytes"))),
+		(None, true)  => {
+			let start = rand::thread_rng().gen::<u32>() as u64;
+			(start, start + SUBADDRESS_INDICES)
+		},
+		(None, false) => (thread as u64, SUBADDRESS_INDICES),
+	};
+	let mut saved = [0_u8; 32];
+	saved[..8].copy_from_slice(&end.to_le_bytes());
+
+	loop {
+		// Create the subaddress `EdwardsPoint`'s.
+		let mut eds: Vec<EdwardsPoint> = Vec::with_capacity(SUBADDRESS_BATCH_SIZE);
+		let mut indices: Vec<u32> = Vec::with_capacity(SUBADDRESS_BATCH_SIZE);
+		while eds.len() < SUBADDRESS_BATCH_SIZE {
+			// Every index has been walked.
+			if minor >= end {
+				break
+			}
+
+			// Random indices wrap around.
+			let index = minor as u32;
+			minor += step;
+
+			// `0/0` is the primary address, not a subaddress.
+			if major == 0 && index == 0 {
+				continue
+			}
+
+			// D = B + m*G
+			let m = subaddress_scalar(&mut data, index);
+			eds.push(spend + &m * &ED25519_BASEPOINT_TABLE);
+			indices.push(index);
+		}
+
+		// Returning drops `to_main`, once every
+		// thread did, `main` knows nothing can match.
+		if eds.is_empty() {
+			break
+		}
+		let tries = eds.len() as u64;
+
+		// Batch compress the `EdwardsPoint`'s.
+		let y_points = EdwardsPoint::batch_compress_edwards(&mut eds);
+
+		for (y, index) in y_points.into_iter().zip(indices) {
+			// Calculate 1st `11` (or up to `44`) characters of the subaddress.
+			let mut bytes = [0_u8; 33];
+			bytes[0] = network_byte;
+			bytes[1..].copy_from_slice(y.as_bytes());
+
+			let mut addr = [0_u8; SPEND_CHARS];
+			let len = crate::encode::encode_blocks(&bytes, blocks, &mut addr);
+
+			// SAFETY:
+			// The input is known UTF-8 compatible bytes.
+			if let Some(pattern) = patterns.find(unsafe { std::str::from_utf8_unchecked(&addr[..len]) }) {
+				// Create the full subaddress.
+				let pair = monero::ViewPair { view, spend: PublicKey { point: spend.compress() } };
+				let index = monero::cryptonote::subaddress::Index { major, minor: index };
+				let address = monero::cryptonote::subaddress::get_subaddress(&pair, index, Some(network.monero()));
+
+				// Send to `main`, it decides when to stop.
+				// Every index is different, so keep walking.
+				let _ = to_main.send(Hit::Subaddress { address: address.to_string(), major: index.major, minor: index.minor, pattern });
+			}
+		}
+
+		// Save where this thread is (and where it ends).
+		save(&positions, thread, saved, minor);
+
+		// Exit if `die` signal is set.
+		if die.load(std::sync::atomic::Ordering::SeqCst) {
+			break
+		}
+
+		// Increment `iteration`.
+		iter.fetch_add(tries, std::sync::atomic::Ordering::SeqCst);
+	}
+}
+
+//---------------------------------------------------------------------------------------------------- Calculate the address tail.
+// Write the checksum into raw address bytes
+// and return the last `18` base58 characters.
+//
+// Standard:   network byte (1) || spend (32) || view (32) || checksum (4)
+// Integrated: network byte (1) || spend (32) || view (32) || payment ID (8) || checksum (4)
+//
+// The tail is the last full `8` byte block (`11` characters)
+// and the last `5` byte block (`7` characters).
+#[inline(always)]
+fn checksum_tail<const N: usize>(data: &mut [u8; N]) -> [u8; 18] {
+	let checksum = monero::cryptonote::hash::keccak_256(&data[..N - 4]);
+	data[N - 4..].copy_from_slice(&checksum[..4]);
+
+	let mut tail = [0_u8; 18];
+	tail[..11].copy_from_slice(&crate::encode::encode_11(&data[N - 13..N - 5]));
+	tail[11..].copy_from_slice(&crate::encode::encode_7(&data[N - 5..]));
+	tail
+}
+
+//---------------------------------------------------------------------------------------------------- Calculate the address suffix.
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+fn calculate_suffix(
+	to_main: std::sync::mpsc::Sender::<Hit>,
+	iter: Arc<AtomicU64>,
+	die: Arc<AtomicBool>,
+	positions: Positions,
+	thread: usize,
+	patterns: Patterns,
+	spend: PrivateKey,
+	network: Network,
+) {
+	// Raw address bytes, only the view key + checksum change.
+	let mut data = [0_u8; STANDARD_LEN];
+	data[0] = network.byte();
+	data[1..33].copy_from_slice(PublicKey::from_private_key(&spend).as_bytes());
+
+	// Seed + thread local iteration count,
+	// either resumed from a checkpoint or random.
+	let (mut seed, mut tries) = seed_and_tries(&positions, thread);
+
+	// Base Point.
+	let mut point = &(seed + Scalar::from(tries)) * &ED25519_BASEPOINT_TABLE;
+
+	// Offset.
+	let offset = &Scalar::from(1_u8) * &ED25519_BASEPOINT_TABLE;
+
+	loop {
+		// Batch compress the `EdwardsPoint`'s.
+		let mut eds: Vec<EdwardsPoint> = Vec::with_capacity(BATCH_SIZE);
+		for _ in 0..BATCH_SIZE {
+			eds.push(point);
+			point += offset;
+		}
+		let y_points = EdwardsPoint::batch_compress_edwards(&mut eds);
+
+		// How many keys of this batch were actually tried.
+		let mut checked = 0_u64;
+
+		// Iterate over `CompressedEdwardsY` (public view key)
+		for y in y_points {
+			checked += 1;
+			data[33..65].copy_from_slice(y.as_bytes());
+			let tail = checksum_tail(&mut data);
+
+			// SAFETY:
+			// The input is known UTF-8 compatible bytes.
+			if let Some(pattern) = patterns.find(unsafe { std::str::from_utf8_unchecked(&tail[..]) }) {
+				// Create Private Spend/View Keypair.
+				let view = PrivateKey { scalar: seed + Scalar::from(tries) };
+				let pair = KeyPair { view, spend };
+				let address = Address::from_keypair(network.monero(), &pair);
+
+				// Send to `main`, it decides when to stop.
+				let _ = to_main.send(Hit::Wallet { address: address.to_string(), spend, view, pattern });
+
+				// Reseed, the rest of this batch belongs to the old seed.
+				seed  = rand_scalar();
+				point = &seed * &ED25519_BASEPOINT_TABLE;
+				tries = 0;
+				break
+			}
+
+			tries += 1;
+		}
+
+		// Save where this thread is.
+		save(&positions, thread, seed.to_bytes(), tries);
+
+		// Exit if `die` signal is set.
+		if die.load(std::sync::atomic::Ordering::SeqCst) {
+			break
+		}
+
+		// Increment `iteration`.
+		iter.fetch_add(checked, std::sync::atomic::Ordering::SeqCst);
+	}
+}
+
+//---------------------------------------------------------------------------------------------------- Calculate the integrated address.
+// Write the `payment_id` into the raw integrated address
+// bytes and return its last `18` base58 characters.
+#[inline(always)]
+fn integrated_tail(data: &mut [u8; INTEGRATED_LEN], payment_id: u64) -> [u8; 18] {
+	data[65..73].copy_from_slice(&payment_id.to_be_bytes());
+	checksum_tail(data)
+}
+
+/// The loop of 1 [`spawn_integrated_workers()`] thread, it returns once `die` is set.
+#[inline(always)]
+#[allow(clippy::too_many_arguments)]
+pub fn calculate_integrated(
+	to_main: std::sync::mpsc::Sender::<Hit>,
+	iter: Arc<AtomicU64>,
+	die: Arc<AtomicBool>,
+	positions: Positions,
+	thread: usize,
+	patterns: Patterns,
+	spend: PublicKey,
+	view: PublicKey,
+	network: Network,
+) {
+	// Raw address bytes, only the payment ID + checksum change.
+	let mut data = [0_u8; INTEGRATED_LEN];
+	data[0] = network.integrated_byte();
+	data[1..33].copy_from_slice(spend.as_bytes());
+	data[33..65].copy_from_slice(view.as_bytes());
+
+	// Each thread starts at a random payment ID (or where it was).
+	let mut payment_id: u64 = match resume(&positions, thread) {
+		Some(p) => p.offset,
+		None    => rand::thread_rng().gen(),
+	};
+
+	loop {
+		for _ in 0..BATCH_SIZE {
+			let tail = integrated_tail(&mut data, payment_id);
+
+			// SAFETY:
+			// The input is known UTF-8 compatible bytes.
+			if let Some(pattern) = patterns.find(unsafe { std::str::from_utf8_unchecked(&tail[..]) }) {
+				let id = monero::util::address::PaymentId(payment_id.to_be_bytes());
+				let address = Address::integrated(network.monero(), spend, view, id);
+				let payment_id = payment_id.to_be_bytes().iter().map(|b| format!("{b:02x}")).collect();
+
+				// Send to `main`, it decides when to stop.
+				// Every payment ID is different, so keep counting.
+				let _ = to_main.send(Hit::Integrated { address: address.to_string(), payment_id, pattern });
+			}
+
+			payment_id = payment_id.wrapping_add(1);
+		}
+
+		// Save where this thread is.
+		save(&positions, thread, [0; 32], payment_id);
+
+		// Exit if `die` signal is set.
+		if die.load(std::sync::atomic::Ordering::SeqCst) {
+			break
+		}
+
 		// Increment `iteration`.
 		iter.fetch_add(BATCH_SIZE_U64, std::sync::atomic::Ordering::SeqCst);
 	}
 }
 
 //---------------------------------------------------------------------------------------------------- TESTS
-//#[cfg(test)]
-//mod tests {
-//  #[test]
-//  fn __TEST__() {
-//  }
-//}
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::checkpoint;
+
+	// Known keys/address from `monero-wallet-cli`
+	// (the wallet used in `monero/tests/functional_tests`).
+	const SPEND:   &str = "148d78d2aba7dbca5cd8f6abcfb0b3c009ffbdbea1ff373d50ed94d78286640e";
+	const VIEW:    &str = "49774391fa5e8d249fc2c5b45dadef13534bf2483dede880dac88f061e809100";
+	const ADDRESS: &str = "42ey1afDFnn4886T7196doS9GPMzexD9gXpsZJDwVjeRVdFCSoHnv7KPbBeGpzJBzHRCAs9UxqeoyFQMYbqSWYTfJJQAWDm";
+
+	#[test]
+	fn derive_view_key() {
+		let spend = PrivateKey::from_str(SPEND).unwrap();
+		let view  = view_from_spend(&spend);
+		assert_eq!(view.to_string(), VIEW);
+
+		let address = Address::from_keypair(monero::Network::Mainnet, &KeyPair { view, spend });
+		assert_eq!(address.to_string(), ADDRESS);
+	}
+
+	#[test]
+	fn join_split_key_derives_view_key() {
+		// Split the known spend key into 2 parts.
+		let spend = PrivateKey::from_str(SPEND).unwrap();
+		let key_1 = rand_priv();
+		let key_2 = PrivateKey { scalar: spend.scalar - key_1.scalar };
+
+		let (address, spend, view) = join_split_key(&[key_1, key_2], false, Network::Mainnet);
+		assert_eq!(address, ADDRESS);
+		assert_eq!(spend,   SPEND);
+		assert_eq!(view,    VIEW);
+	}
+
+	#[test]
+	fn join_split_key_many_parts() {
+		let spend = PrivateKey::from_str(SPEND).unwrap();
+		let parts: Vec<PrivateKey> = (0..4).map(|_| rand_priv()).collect();
+		let last = PrivateKey { scalar: spend.scalar - parts.iter().map(|p| p.scalar).sum::<Scalar>() };
+
+		let keys: Vec<PrivateKey> = parts.into_iter().chain([last]).collect();
+		let (address, spend, _) = join_split_key(&keys, false, Network::Mainnet);
+		assert_eq!(address, ADDRESS);
+		assert_eq!(spend,   SPEND);
+	}
+
+	#[test]
+	fn join_split_key_network() {
+		let spend = PrivateKey::from_str(SPEND).unwrap();
+		let view  = view_from_spend(&spend);
+		let key_1 = rand_priv();
+		let key_2 = PrivateKey { scalar: spend.scalar - key_1.scalar };
+
+		for network in [Network::Mainnet, Network::Stagenet, Network::Testnet] {
+			let (address, _, _) = join_split_key(&[key_1, key_2], false, network);
+			let expected = Address::from_keypair(network.monero(), &KeyPair { view, spend });
+			assert_eq!(address, expected.to_string());
+		}
+	}
+
+	#[test]
+	// The `11` characters the workers match against must
+	// be the start of the real address on every network.
+	fn first_11_characters() {
+		for network in [Network::Mainnet, Network::Stagenet, Network::Testnet] {
+			for _ in 0..100 {
+				let spend = rand_priv();
+				let y = (&spend.scalar * &ED25519_BASEPOINT_TABLE).compress();
+
+				let mut bytes = [0_u8; 11];
+				bytes[0] = network.byte();
+				bytes[1..].copy_from_slice(&y.as_bytes()[0..10]);
+				let addr = crate::encode::encode_11(&bytes);
+
+				let view = view_from_spend(&spend);
+				let address = Address::from_keypair(network.monero(), &KeyPair { view, spend }).to_string();
+				assert_eq!(std::str::from_utf8(&addr).unwrap(), &address[..11]);
+			}
+		}
+	}
+
+	#[test]
+	// A batch that stops at a match only counts the keys it tried.
+	fn tries_counted_on_match() {
+		let (to, from) = std::sync::mpsc::channel();
+		let iter = Arc::new(AtomicU64::new(0));
+		let die = Arc::new(AtomicBool::new(false));
+		let positions = checkpoint::positions(1, None);
+		let patterns = Patterns::new(["^."]).unwrap();
+
+		let worker = {
+			let (iter, die) = (iter.clone(), die.clone());
+			std::thread::spawn(move || calculate(to, iter, die, positions, 0, patterns, None, false, Network::Mainnet))
+		};
+		for _ in 0..3 {
+			from.recv().unwrap();
+		}
+		die.store(true, std::sync::atomic::Ordering::SeqCst);
+		worker.join().unwrap();
+
+		// Every batch matches its 1st key, the last one isn't counted.
+		let hits = 3 + from.try_iter().count() as u64;
+		assert_eq!(iter.load(std::sync::atomic::Ordering::SeqCst), hits - 1);
+	}
+
+	// `monero-rs` subaddress test vector.
+	const SUB_VIEW:    &str = "77916d0cd56ed1920aef6ca56d8a41bac915b68e4c46a589e0956e27a7b77404";
+	const SUB_SPEND:   &str = "8163466f1883598e6dd14027b8da727057165da91485834314f5500a65846f09";
+	const SUBADDRESS:  &str = "89pMNxzcCo5LAPZDX4qaTeanA6ZiS3VRdUbeKHzbDZkD1Q3YsDDfmXbT2zyjLeHWuuN4vxKne8kNpjH3cMk7nmhwSALCxsd";
+
+	#[test]
+	fn subaddress_scalar_matches() {
+		let view = PrivateKey::from_str(SUB_VIEW).unwrap();
+		let mut data = [0_u8; 48];
+		data[..8].copy_from_slice(SUBADDRESS_PREFIX);
+		data[8..40].copy_from_slice(view.as_bytes());
+
+		for major in [0, 2, u32::MAX] {
+			data[40..44].copy_from_slice(&major.to_le_bytes());
+			for minor in [1, 18, 12345] {
+				let index = monero::cryptonote::subaddress::Index { major, minor };
+				let expected = monero::cryptonote::subaddress::get_secret_scalar(&view, index);
+				assert_eq!(subaddress_scalar(&mut data, minor), expected.scalar);
+			}
+		}
+	}
+
+	#[test]
+	fn subaddress_search() {
+		let view  = PrivateKey::from_str(SUB_VIEW).unwrap();
+		let spend = PrivateKey::from_str(SUB_SPEND).unwrap();
+		let point = &spend.scalar * &ED25519_BASEPOINT_TABLE;
+
+		// Look for the known subaddress at `2/18`.
+		let (to, from) = std::sync::mpsc::channel();
+		let iter  = Arc::new(AtomicU64::new(0));
+		let die   = Arc::new(AtomicBool::new(false));
+		let patterns = Patterns::new([&format!("^{}$", &SUBADDRESS[..11])]).unwrap();
+		spawn_subaddress_workers(2, &to, &iter, &die, &checkpoint::positions(2, None), &patterns, view, point, 2, false, Network::Mainnet);
+
+		let hit = from.recv().unwrap();
+		die.store(true, std::sync::atomic::Ordering::SeqCst);
+		assert_eq!(hit, Hit::Subaddress { address: SUBADDRESS.into(), major: 2, minor: 18, pattern: 0 });
+	}
+
+	#[test]
+	fn subaddress_skips_primary() {
+		let view  = PrivateKey::from_str(SUB_VIEW).unwrap();
+		let spend = PrivateKey::from_str(SUB_SPEND).unwrap();
+		let point = &spend.scalar * &ED25519_BASEPOINT_TABLE;
+
+		// The first try of thread `0` must be `0/1`, not `0/0`.
+		let (to, from) = std::sync::mpsc::channel();
+		let iter  = Arc::new(AtomicU64::new(0));
+		let die   = Arc::new(AtomicBool::new(false));
+		let patterns = Patterns::new(["^8"]).unwrap();
+		spawn_subaddress_workers(1, &to, &iter, &die, &checkpoint::positions(1, None), &patterns, view, point, 0, false, Network::Mainnet);
+
+		let hit = from.recv().unwrap();
+		die.store(true, std::sync::atomic::Ordering::SeqCst);
+		let pair = monero::ViewPair { view, spend: PublicKey::from_private_key(&spend) };
+		let index = monero::cryptonote::subaddress::Index { major: 0, minor: 1 };
+		let expected = monero::cryptonote::subaddress::get_subaddress(&pair, index, None);
+		assert_eq!(hit, Hit::Subaddress { address: expected.to_string(), major: 0, minor: 1, pattern: 0 });
+	}
+
+	#[test]
+	// Workers return once every index was walked, which disconnects the channel.
+	fn subaddress_exhausted() {
+		let view  = PrivateKey::from_str(SUB_VIEW).unwrap();
+		let spend = PrivateKey::from_str(SUB_SPEND).unwrap();
+		let point = &spend.scalar * &ED25519_BASEPOINT_TABLE;
+
+		// Resume 10 indices before the end.
+		let mut seed = [0_u8; 32];
+		seed[..8].copy_from_slice(&SUBADDRESS_INDICES.to_le_bytes());
+		let position = checkpoint::Position { seed, offset: SUBADDRESS_INDICES - 10 };
+		let positions = checkpoint::positions(1, Some(&[Some(position)]));
+
+		let (to, from) = std::sync::mpsc::channel();
+		let iter  = Arc::new(AtomicU64::new(0));
+		let die   = Arc::new(AtomicBool::new(false));
+		let patterns = Patterns::new(["^8zzzzzzzzzz"]).unwrap();
+		spawn_subaddress_workers(1, &to, &iter, &die, &positions, &patterns, view, point, 0, false, Network::Mainnet);
+		drop(to);
+
+		assert!(from.recv().is_err());
+		assert_eq!(iter.load(std::sync::atomic::Ordering::SeqCst), 10);
+	}
+
+	#[test]
+	fn random_view_key() {
+		let spend = PrivateKey::from_str(SPEND).unwrap();
+		assert_ne!(view_key(&spend, true).to_string(), VIEW);
+		assert_eq!(view_key(&spend, false).to_string(), VIEW);
+	}
+
+	#[test]
+	// The `18` characters the integrated workers match against
+	// must be the end of the real address on every network.
+	fn integrated_tail_matches() {
+		let spend = PrivateKey::from_str(SPEND).unwrap();
+		let view  = view_from_spend(&spend);
+		let (spend, view) = (PublicKey::from_private_key(&spend), PublicKey::from_private_key(&view));
+
+		for network in [Network::Mainnet, Network::Stagenet, Network::Testnet] {
+			let mut data = [0_u8; INTEGRATED_LEN];
+			data[0] = network.integrated_byte();
+			data[1..33].copy_from_slice(spend.as_bytes());
+			data[33..65].copy_from_slice(view.as_bytes());
+
+			for _ in 0..100 {
+				let payment_id: u64 = rand::thread_rng().gen();
+				let tail = integrated_tail(&mut data, payment_id);
+
+				let id = monero::util::address::PaymentId(payment_id.to_be_bytes());
+				let address = Address::integrated(network.monero(), spend, view, id).to_string();
+				assert_eq!(address.len(), 106);
+				assert_eq!(std::str::from_utf8(&tail).unwrap(), &address[88..]);
+			}
+		}
+	}
+
+	#[test]
+	fn integrated_search() {
+		let spend = PrivateKey::from_str(SPEND).unwrap();
+		let view  = view_from_spend(&spend);
+		let (spend, view) = (PublicKey::from_private_key(&spend), PublicKey::from_private_key(&view));
+
+		let (to, from) = std::sync::mpsc::channel();
+		let iter  = Arc::new(AtomicU64::new(0));
+		let die   = Arc::new(AtomicBool::new(false));
+		let patterns = Patterns::new(["ab$"]).unwrap();
+		spawn_integrated_workers(2, &to, &iter, &die, &checkpoint::positions(2, None), &patterns, spend, view, Network::Mainnet);
+
+		let Hit::Integrated { address, payment_id, .. } = from.recv().unwrap() else { panic!("not an integrated hit") };
+		die.store(true, std::sync::atomic::Ordering::SeqCst);
+		assert!(address.ends_with("ab"));
+
+		// The address must decode to the same keys + payment ID.
+		let address = Address::from_str(&address).unwrap();
+		assert_eq!(address.public_spend, spend);
+		assert_eq!(address.public_view, view);
+		match address.addr_type {
+			monero::AddressType::Integrated(id) => {
+				let id: String = id.as_bytes().iter().map(|b| format!("{b:02x}")).collect();
+				assert_eq!(id, payment_id);
+			},
+			_ => panic!("not an integrated address"),
+		}
+	}
+
+	#[test]
+	// A pattern reaching into the 2nd block must be matched there.
+	fn search_beyond_11_characters() {
+		let (to, from) = std::sync::mpsc::channel();
+		let iter  = Arc::new(AtomicU64::new(0));
+		let die   = Arc::new(AtomicBool::new(false));
+		let patterns = Patterns::new(["^.{11}a.*$"]).unwrap();
+		assert_eq!(patterns.blocks(), 2);
+		spawn_workers(2, &to, &iter, &die, &checkpoint::positions(2, None), &patterns, None, false, Network::Mainnet);
+
+		let Hit::Wallet { address, spend, view, .. } = from.recv().unwrap() else { panic!("not a wallet hit") };
+		die.store(true, std::sync::atomic::Ordering::SeqCst);
+		assert_eq!(&address[11..12], "a");
+
+		let pair = KeyPair { view, spend };
+		assert_eq!(Address::from_keypair(monero::Network::Mainnet, &pair).to_string(), address);
+	}
+
+	#[test]
+	// A pattern without `^` can match past the 1st block, so every block must be encoded.
+	fn search_unanchored_beyond_11_characters() {
+		let (to, from) = std::sync::mpsc::channel();
+		let iter  = Arc::new(AtomicU64::new(0));
+		let die   = Arc::new(AtomicBool::new(false));
+		let patterns = Patterns::new([".{11}a"]).unwrap();
+		assert_eq!(patterns.blocks(), 4);
+		spawn_workers(2, &to, &iter, &die, &checkpoint::positions(2, None), &patterns, None, false, Network::Mainnet);
+
+		let Hit::Wallet { address, spend, view, .. } = from.recv().unwrap() else { panic!("not a wallet hit") };
+		die.store(true, std::sync::atomic::Ordering::SeqCst);
+		assert!(address[11..44].contains('a'));
+
+		let pair = KeyPair { view, spend };
+		assert_eq!(Address::from_keypair(monero::Network::Mainnet, &pair).to_string(), address);
+	}
+
+	#[test]
+	// The `18` characters the suffix workers match against
+	// must be the end of the real address on every network.
+	fn suffix_tail_matches() {
+		for network in [Network::Mainnet, Network::Stagenet, Network::Testnet] {
+			let spend = rand_priv();
+			let mut data = [0_u8; STANDARD_LEN];
+			data[0] = network.byte();
+			data[1..33].copy_from_slice(PublicKey::from_private_key(&spend).as_bytes());
+
+			for _ in 0..100 {
+				let view = rand_priv();
+				data[33..65].copy_from_slice(PublicKey::from_private_key(&view).as_bytes());
+				let tail = checksum_tail(&mut data);
+
+				let address = Address::from_keypair(network.monero(), &KeyPair { view, spend }).to_string();
+				assert_eq!(std::str::from_utf8(&tail).unwrap(), &address[77..]);
+			}
+		}
+	}
+
+	#[test]
+	fn suffix_search() {
+		let spend = PrivateKey::from_str(SPEND).unwrap();
+
+		let (to, from) = std::sync::mpsc::channel();
+		let iter  = Arc::new(AtomicU64::new(0));
+		let die   = Arc::new(AtomicBool::new(false));
+		let patterns = Patterns::new(["ab$"]).unwrap();
+		spawn_suffix_workers(2, &to, &iter, &die, &checkpoint::positions(2, None), &patterns, spend, Network::Mainnet);
+
+		let Hit::Wallet { address, spend: spend_found, view, .. } = from.recv().unwrap() else { panic!("not a wallet hit") };
+		die.store(true, std::sync::atomic::Ordering::SeqCst);
+		assert!(address.ends_with("ab"));
+		assert_eq!(spend_found, spend);
+		// The spend key (and so the start of the address) stays the same.
+		assert_eq!(&address[..44], &ADDRESS[..44]);
+
+		let pair = KeyPair { view, spend };
+		assert_eq!(Address::from_keypair(monero::Network::Mainnet, &pair).to_string(), address);
+	}
+
+	#[test]
+	// Phase 1 finds the spend key, phase 2 keeps it
+	// and finds the view key, both ends must match.
+	fn prefix_then_suffix() {
+		let (to, from) = std::sync::mpsc::channel();
+		let iter  = Arc::new(AtomicU64::new(0));
+		let die   = Arc::new(AtomicBool::new(false));
+		spawn_workers(1, &to, &iter, &die, &checkpoint::positions(1, None), &Patterns::new(["^..a.*$"]).unwrap(), None, false, Network::Mainnet);
+		let Hit::Wallet { spend, .. } = from.recv().unwrap() else { panic!("not a wallet hit") };
+		die.store(true, std::sync::atomic::Ordering::SeqCst);
+
+		let (to, from) = std::sync::mpsc::channel();
+		let iter  = Arc::new(AtomicU64::new(0));
+		let die   = Arc::new(AtomicBool::new(false));
+		spawn_suffix_workers(1, &to, &iter, &die, &checkpoint::positions(1, None), &Patterns::new(["b$"]).unwrap(), spend, Network::Mainnet);
+		let Hit::Wallet { address, spend: spend_found, view, .. } = from.recv().unwrap() else { panic!("not a wallet hit") };
+		die.store(true, std::sync::atomic::Ordering::SeqCst);
+
+		assert_eq!(&address[2..3], "a");
+		assert!(address.ends_with('b'));
+		assert_eq!(spend_found, spend);
+		let pair = KeyPair { view, spend };
+		assert_eq!(Address::from_keypair(monero::Network::Mainnet, &pair).to_string(), address);
+	}
+
+	#[test]
+	// The index of the matching pattern is sent along.
+	fn multiple_patterns() {
+		let (to, from) = std::sync::mpsc::channel();
+		let iter  = Arc::new(AtomicU64::new(0));
+		let die   = Arc::new(AtomicBool::new(false));
+		let patterns = Patterns::new(["^..a.*$", "^..b.*$"]).unwrap();
+		spawn_workers(1, &to, &iter, &die, &checkpoint::positions(1, None), &patterns, None, false, Network::Mainnet);
+
+		let mut hits = [false; 2];
+		while hits != [true; 2] {
+			let hit = from.recv().unwrap();
+			let pattern = hit.pattern();
+			assert_eq!(&hit.address()[2..3], ["a", "b"][pattern]);
+			hits[pattern] = true;
+			// Only find each pattern once.
+			patterns.finish(pattern);
+		}
+		die.store(true, std::sync::atomic::Ordering::SeqCst);
+	}
+
+	#[test]
+	// Workers keep going after a match and reseed, so every match is different.
+	fn workers_keep_going() {
+		let (to, from) = std::sync::mpsc::channel();
+		let iter  = Arc::new(AtomicU64::new(0));
+		let die   = Arc::new(AtomicBool::new(false));
+		spawn_workers(1, &to, &iter, &die, &checkpoint::positions(1, None), &Patterns::new(["^4"]).unwrap(), None, false, Network::Mainnet);
+
+		let found: Vec<String> = (0..5).map(|_| match from.recv().unwrap() {
+			Hit::Wallet { spend, .. } => spend.to_string(),
+			hit => panic!("not a wallet hit: {hit:?}"),
+		}).collect();
+		die.store(true, std::sync::atomic::Ordering::SeqCst);
+		for (i, key) in found.iter().enumerate() {
+			assert!(!found[i + 1..].contains(key));
+		}
+	}
+
+	#[test]
+	// A resumed worker continues from its saved `seed + offset`.
+	fn resume_from_position() {
+		let seed = rand_priv();
+		let position = checkpoint::Position { seed: seed.scalar.to_bytes(), offset: 12345 };
+		let positions = checkpoint::positions(1, Some(&[Some(position)]));
+
+		let (to, from) = std::sync::mpsc::channel();
+		let iter  = Arc::new(AtomicU64::new(0));
+		let die   = Arc::new(AtomicBool::new(false));
+		spawn_workers(1, &to, &iter, &die, &positions, &Patterns::new(["^4"]).unwrap(), None, false, Network::Mainnet);
+
+		let Hit::Wallet { spend, .. } = from.recv().unwrap() else { panic!("not a wallet hit") };
+		die.store(true, std::sync::atomic::Ordering::SeqCst);
+		assert_eq!(spend, PrivateKey { scalar: seed.scalar + Scalar::from(12345_u64) });
+	}
+}
@@ -0,0 +1,161 @@
+//---------------------------------------------------------------------------------------------------- Use
+use crate::vault::Vault;
+use serde::{Serialize,Deserialize};
+use std::path::{Path,PathBuf};
+use std::sync::{Arc,Mutex};
+use std::time::{Duration,Instant};
+
+//---------------------------------------------------------------------------------------------------- Constants
+/// The checkpoint format version.
+const VERSION: u32 = 1;
+
+//---------------------------------------------------------------------------------------------------- Position
+/// Where a worker is in its search.
+///
+/// Spend/view key searches walk `seed + offset`,
+/// subaddress/integrated searches only use `offset`
+/// (the minor index/payment ID), subaddress searches
+/// keep the index they end at in the first 8 `seed` bytes.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Position {
+	pub seed: [u8; 32],
+	pub offset: u64,
+}
+
+/// The [`Position`] of every worker, updated after every batch.
+///
+/// A worker starts from its slot if it is `Some` (resuming).
+pub type Positions = Arc<[Mutex<Option<Position>>]>;
+
+/// `threads` slots, filled with the `resume` positions if given.
+pub fn positions(threads: usize, resume: Option<&[Option<Position>]>) -> Positions {
+	(0..threads)
+		.map(|i| Mutex::new(resume.and_then(|r| r.get(i).copied().flatten())))
+		.collect()
+}
+
+//---------------------------------------------------------------------------------------------------- Checkpoint
+/// A saved search, written every so often to an encrypted file.
+///
+/// Worker seeds are private key material, so the file is always encrypted.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Checkpoint {
+	/// The format version.
+	pub version: u32,
+	/// The original command line arguments, which describe the search.
+	pub args: Vec<String>,
+	/// Total tries across every run.
+	pub tries: u64,
+	/// Total seconds spent searching across every run.
+	pub elapsed: f64,
+	/// How many addresses were found.
+	pub found: u64,
+	/// Which patterns are finished (`--once`).
+	pub done: Vec<bool>,
+	/// Where every worker was (`None` if it hadn't finished a batch yet).
+	pub positions: Vec<Option<Position>>,
+}
+
+impl Checkpoint {
+	/// A checkpoint of the current search.
+	pub fn new(args: Vec<String>, tries: u64, elapsed: f64, found: u64, done: Vec<bool>, positions: &Positions) -> Self {
+		Self {
+			version: VERSION,
+			args,
+			tries,
+			elapsed,
+			found,
+			done,
+			positions: positions.iter().map(|p| *p.lock().unwrap()).collect(),
+		}
+	}
+
+	/// Encrypt and (atomically) write to `path`.
+	pub fn save(&self, path: &Path, vault: &Vault) -> Result<(), &'static str> {
+		let json = serde_json::to_vec(self).expect("checkpoints always serialize");
+		crate::vault::write_atomic(path, &vault.seal(&json)).map_err(|_| "Could not write checkpoint file")
+	}
+
+	/// Read and decrypt a checkpoint from `path`.
+	pub fn load(path: &Path, password: &str) -> Result<(Self, Vault), &'static str> {
+		let data = std::fs::read(path).map_err(|_| "Could not read checkpoint file")?;
+		let (vault, json) = Vault::open(password, &data)?;
+		let checkpoint: Self = serde_json::from_slice(&json).map_err(|_| "Checkpoint file is corrupted")?;
+		if checkpoint.version != VERSION {
+			return Err("Checkpoint file is from an incompatible version");
+		}
+		Ok((checkpoint, vault))
+	}
+
+	/// The checkpoint as JSON, without the worker seeds.
+	pub fn redacted(&self) -> String {
+		let mut json = serde_json::to_value(self).expect("checkpoints always serialize");
+		for position in json["positions"].as_array_mut().into_iter().flatten() {
+			if let Some(position) = position.as_object_mut() {
+				position.remove("seed");
+			}
+		}
+		json.to_string()
+	}
+}
+
+//---------------------------------------------------------------------------------------------------- Saver
+/// Saves a [`Checkpoint`] of a running search every `interval`.
+pub struct Saver {
+	/// Where the checkpoint is written.
+	pub path: PathBuf,
+	/// The key it is encrypted with.
+	pub vault: Vault,
+	/// The original command line arguments.
+	pub args: Vec<String>,
+	/// How often to save.
+	pub interval: Duration,
+	/// When it was last saved.
+	pub last: Instant,
+}
+
+impl Saver {
+	/// Is it time to save again?
+	pub fn due(&self) -> bool {
+		self.last.elapsed() >= self.interval
+	}
+
+	/// Save the current search.
+	pub fn save(&mut self, tries: u64, elapsed: Duration, found: u64, done: Vec<bool>, positions: &Positions) -> Result<(), &'static str> {
+		self.last = Instant::now();
+		Checkpoint::new(self.args.clone(), tries, elapsed.as_secs_f64(), found, done, positions).save(&self.path, &self.vault)
+	}
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn save_and_load() {
+		let positions = positions(2, Some(&[Some(Position { seed: [1; 32], offset: 5 })]));
+		assert_eq!(*positions[0].lock().unwrap(), Some(Position { seed: [1; 32], offset: 5 }));
+		assert_eq!(*positions[1].lock().unwrap(), None);
+
+		let args = vec!["monero-vanity".into(), "--pattern".into(), "hinto".into()];
+		let checkpoint = Checkpoint::new(args, 20_000, 1.5, 1, vec![false], &positions);
+		assert_eq!(checkpoint.positions[1], None);
+
+		let path = std::env::temp_dir().join(format!("monero-vanity-checkpoint-{}", std::process::id()));
+		checkpoint.save(&path, &Vault::new("hunter2").unwrap()).unwrap();
+		assert!(Checkpoint::load(&path, "hunter3").is_err());
+		let (loaded, _) = Checkpoint::load(&path, "hunter2").unwrap();
+		std::fs::remove_file(&path).unwrap();
+		assert_eq!(loaded, checkpoint);
+	}
+
+	#[test]
+	fn redacted() {
+		let positions = positions(2, Some(&[Some(Position { seed: [1; 32], offset: 5 })]));
+		let checkpoint = Checkpoint::new(vec!["monero-vanity".into()], 20_000, 1.5, 1, vec![false], &positions);
+		let json = checkpoint.redacted();
+		assert!(!json.contains("seed"));
+		assert!(json.contains(r#""positions":[{"offset":5},null]"#));
+	}
+}
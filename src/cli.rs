@@ -1,6 +1,5 @@
 //---------------------------------------------------------------------------------------------------- Use
-use clap::Parser;
-use std::process::exit;
+use clap::{Parser,Subcommand,ArgGroup};
 use std::str::FromStr;
 use crate::constants::{
 	VERSION,
@@ -12,8 +11,24 @@ use crate::threads::{
 	THREADS_MAX,
 };
 use crate::state::State;
-use regex::Regex;
+use crate::mnemonic::Language;
+use crate::network::Network;
+use crate::pattern::{PatternType,TailType};
+use crate::regexes::Patterns;
+use crate::leet::Leet;
+use crate::difficulty::{Chance,Target};
+use crate::checkpoint::{Checkpoint,Saver};
+use crate::results::Results;
+use crate::wallet::WalletDir;
+use crate::output::{Output,Event,Found,ExitCode};
+use crate::queue::{Queue,Schedule,Spec,Job,Update};
+use crate::queue::Error as JobError;
+use crate::mode::Mode;
+use crate::hit::Hit;
+use crate::vault::Purpose;
 use std::io::Write;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool,AtomicU64};
 use readable::{
 	Unsigned,
 	Time,
@@ -24,38 +39,111 @@ const ABOUT: &str =
 r#"monero-vanity automatically prefixes your input
 with `^..` and suffixes it with `.*$` so that
 your PATTERN starts from the 3rd character
-until the 11th character of the address.
+of the address. Patterns can reach until the 44th
+character, the rest is controlled by the view key.
+
+The first 2 characters are (mostly) fixed by the network:
+Mainnet addresses start with `4`,
+Stagenet addresses start with `5`,
+Testnet addresses start with `9` or `A`.
 
 Example input: `hinto`
 Actual regex used: `^..hinto.*$`
 
+With `--integrated` or `--suffix`, the PATTERN is matched
+against the last 18 characters of the address instead,
+which are changed by brute-forcing the payment ID
+(integrated) or the private view key (suffix).
+
+Example input: `hinto` with `--tail suffix`
+Actual regex used: `hinto$`
+
 To disable this, use `--first`.
 Warning: this puts you in full control of the regex,
 you can input any value, even an impossible one."#;
 
 #[derive(Parser, Debug)]
 #[command(version = VERSION_COMMIT, override_usage = "monero-vanity [--OPTIONS]", long_about = ABOUT)]
+#[command(group(ArgGroup::new("tail_modes").args(["integrated", "suffix", "suffix_pattern"])))]
+#[command(group(ArgGroup::new("tail_users").args(["integrated", "suffix", "suffix_pattern", "job", "queue"]).multiple(true)))]
+#[command(group(ArgGroup::new("jobs").args(["job", "queue"]).multiple(true).conflicts_with_all([
+	"pattern", "pattern_file", "once", "count", "infinite", "calculate_split_key", "subaddress",
+	"integrated", "suffix", "suffix_pattern", "checkpoint", "encrypt", "wallet_dir",
+])))]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct Cli {
+	#[command(subcommand)]
+	command: Option<Command>,
+
 	/// How many threads to use.
 	///
 	/// Will use half threads if no number or 0 is given.
 	#[arg(long, short, default_value_t = *THREADS_HALF)]
 	threads: usize,
 
-	/// Address regex pattern to look for
+	/// Address regex pattern(s) to look for
 	///
 	/// E.g: `hinto` would find an address: `44hinto...`
-	#[arg(long, short, default_value_t = String::new())]
-	pattern: String,
+	///
+	/// Multiple patterns are searched at the same time: `--pattern hinto janai`
+	#[arg(long, short, num_args(1..))]
+	pattern: Vec<String>,
+
+	/// Read more address patterns from a file (1 per line, `#` for comments)
+	#[arg(long, value_name = "FILE")]
+	pattern_file: Option<std::path::PathBuf>,
+
+	/// Stop looking for these patterns after their first hit (every pattern if none are given)
+	#[arg(long, value_name = "PATTERN", num_args = 0..)]
+	once: Option<Vec<String>>,
 
 	/// Start from 1st character instead of: ^..PATTERN.*$
 	#[arg(long, short)]
 	first: bool,
 
+	/// Match every base58 version of each letter (`hinto` also finds `HiNTo`)
+	///
+	/// Letters base58 doesn't have are skipped: `l` only matches `L`, `o` only `o`.
+	#[arg(long)]
+	ignore_case: bool,
+
+	/// Also match leetspeak/lookalikes of each character (`hello` -> `h[e3][L1][L1]o`)
+	#[arg(long)]
+	leet: bool,
+
+	/// Read the `--leet` substitution table from a file (`e = e3`, 1 per line)
+	#[arg(long, value_name = "FILE")]
+	leet_file: Option<std::path::PathBuf>,
+
+	/// Which Monero network to generate addresses for
+	#[arg(long, short, value_enum, default_value_t = Network::default())]
+	network: Network,
+
+	/// How many addresses to find before exiting
+	///
+	/// Defaults to 1, or every pattern once with `--once`.
+	#[arg(long, value_parser = clap::value_parser!(u64).range(1..), conflicts_with = "suffix_pattern")]
+	count: Option<u64>,
+
+	/// Keep finding addresses until stopped (Ctrl+C)
+	#[arg(long, conflicts_with_all = ["count", "suffix_pattern"])]
+	infinite: bool,
+
 	/// How many milliseconds in-between output refreshes
 	#[arg(long, short, default_value_t = 500)]
 	refresh: u64,
 
+	/// Decode a 25-word mnemonic seed and print its keys and address
+	#[arg(long, value_name = "SEED")]
+	check_seed: Option<String>,
+
+	/// Use a random private view key instead of deriving it from the spend key
+	///
+	/// The wallet must then be restored with `--generate-from-keys`
+	/// instead of `--generate-from-spend-key`.
+	#[arg(long)]
+	random_view_key: bool,
+
 	/// Generates a new split key that can be given out to allow others
 	/// to help you find an address while keeping the private key hidden
 	///
@@ -66,195 +154,1468 @@ pub struct Cli {
 	/// Calculates addresses for the provided public split
 	/// key instead of our own generated private key
 	///
+	/// Takes the public part of every contributor, they are added together
+	/// so nobody (including the one calculating) knows the final key alone.
+	/// Each part must have the proof of possession `--gen-private-split-key` adds.
+	///
+	/// Or takes a job file made with `split-job`, which has the pattern,
+	/// network and count, the results are written to `NAME.result.json`.
+	///
 	/// (experimental)
-	#[arg(long, short)]
-	calculate_split_key: Option<String>,
+	#[arg(long, short, num_args(1..), value_name = "PUBLIC_PART|JOB_FILE")]
+	calculate_split_key: Option<Vec<String>>,
+
+	/// Finds a subaddress of an existing wallet instead of a new wallet
+	///
+	/// Takes the wallet's private view key and public spend key.
+	#[arg(long, short, num_args(2), value_names = ["PRIVATE_VIEW_KEY", "PUBLIC_SPEND_KEY"], conflicts_with = "calculate_split_key")]
+	subaddress: Option<Vec<String>>,
+
+	/// The account (major index) to search subaddresses in
+	#[arg(long, default_value_t = 0, requires = "subaddress")]
+	major: u32,
+
+	/// Search random subaddress indices instead of walking from index 1
+	#[arg(long, requires = "subaddress")]
+	random_index: bool,
+
+	/// Finds a payment ID that makes an integrated address of an existing wallet match
+	///
+	/// Takes the wallet's standard address, the keys stay fixed.
+	#[arg(long, short, value_name = "ADDRESS", conflicts_with_all = ["calculate_split_key", "subaddress"])]
+	integrated: Option<String>,
+
+	/// Matches the end of a new address by searching view keys instead of spend keys
+	///
+	/// This is much slower, the view key is random so there is no seed.
+	#[arg(long, conflicts_with_all = ["calculate_split_key", "subaddress", "integrated"])]
+	suffix: bool,
+
+	/// After finding the pattern at the start, also match the end with this pattern
+	///
+	/// The spend key found first stays fixed and view keys are searched
+	/// for the suffix, so the address matches both ends (much slower).
+	#[arg(long, value_name = "PATTERN", conflicts_with_all = ["calculate_split_key", "subaddress", "integrated", "suffix"])]
+	suffix_pattern: Option<String>,
+
+	/// Where to match the pattern in the last 18 characters (with `--integrated`, `--suffix` or `--suffix-pattern`)
+	#[arg(long, value_enum, default_value_t = TailType::default(), requires = "tail_users")]
+	tail: TailType,
 
 	/// Joins the private part of a split key with the
 	/// calculated part to get the generated private key
 	///
+	/// The calculated part is the `vanity...` result of `--calculate-split-key`,
+	/// it is only joined if it really adds up to an address matching your own
+	/// `--pattern` (with `--first`, `--ignore-case`, `--leet` and `--network`).
+	/// It can also be the `NAME.result.json` file, every part in it is joined
+	/// if it is for your own `--split-job` file.
+	/// The private part of every contributor is needed.
+	///
 	/// (experimental)
-	#[arg(long, short, num_args(2))]
-	join_split_key: Option<Vec<String>>
+	#[arg(long, short, num_args(2..), value_names = ["CALCULATED_PART", "PRIVATE_PART"])]
+	join_split_key: Option<Vec<String>>,
+
+	/// Your own job file made with `split-job`, what `--join-split-key` is checked against
+	///
+	/// Needed to join a result file, a `vanity...` part can use it instead of `--pattern`.
+	#[arg(long, value_name = "FILE", requires = "join_split_key", conflicts_with_all = ["pattern", "first", "ignore_case", "leet", "leet_file", "network", "random_view_key"])]
+	split_job: Option<std::path::PathBuf>,
+
+	/// Queue a job: `PATTERN [count=N] [network=NETWORK] [mode=standard|integrated] [address=ADDRESS]`
+	///
+	/// Jobs share the same threads and run one after another (or take turns with `--schedule round-robin`).
+	/// `address` is the wallet's standard address of an integrated job, `count` defaults to 1.
+	/// `--first`, `--ignore-case`, `--leet`, `--tail` and `--random-view-key` apply to every job.
+	#[arg(long, value_name = "JOB")]
+	job: Vec<String>,
+
+	/// Read more jobs from a file (1 per line, `#` for comments)
+	#[arg(long, value_name = "FILE")]
+	queue: Option<std::path::PathBuf>,
+
+	/// How the threads move through the jobs: one job after another, or taking turns
+	#[arg(long, value_enum, default_value_t = Schedule::default(), requires = "jobs")]
+	schedule: Schedule,
+
+	/// How many seconds each job runs before the next takes over (`--schedule round-robin`)
+	#[arg(long, value_name = "SECS", default_value_t = 60, value_parser = clap::value_parser!(u64).range(1..), requires = "jobs")]
+	slice: u64,
+
+	/// Periodically save the search to this (encrypted) file so it can be resumed
+	///
+	/// The password is read from `MONERO_VANITY_CHECKPOINT_PASSWORD` or asked for (twice).
+	#[arg(long, value_name = "FILE", conflicts_with = "suffix_pattern")]
+	checkpoint: Option<std::path::PathBuf>,
+
+	/// How many seconds in-between checkpoints
+	#[arg(long, value_name = "SECS", default_value_t = 60, value_parser = clap::value_parser!(u64).range(1..), requires = "checkpoint")]
+	checkpoint_interval: u64,
+
+	/// Resume a search from a `--checkpoint` file
+	///
+	/// The original options are used (except `--output`), cumulative stats are kept.
+	#[arg(long, value_name = "FILE", conflicts_with_all = [
+		"threads", "pattern", "pattern_file", "once", "first", "ignore_case", "leet", "leet_file", "network",
+		"count", "infinite", "refresh", "check_seed", "random_view_key", "gen_private_split_key",
+		"calculate_split_key", "subaddress", "major", "random_index", "integrated", "suffix", "suffix_pattern",
+		"tail", "join_split_key", "split_job", "job", "queue", "schedule", "slice", "checkpoint",
+		"checkpoint_interval", "encrypt", "wallet_dir",
+	])]
+	resume: Option<std::path::PathBuf>,
+
+	/// Write found keys only to this (encrypted) file, the terminal only shows the address
+	///
+	/// Results are appended if the file exists, read them with `monero-vanity decrypt FILE`.
+	/// The password is read from `MONERO_VANITY_RESULTS_PASSWORD` or asked for (twice for a new file).
+	#[arg(long, value_name = "FILE")]
+	encrypt: Option<std::path::PathBuf>,
+
+	/// Also write every found wallet as a `.keys` file to this directory
+	///
+	/// Open them with `monero-wallet-cli --wallet-file DIR/NAME`, no restoring needed.
+	/// The wallet password is read from `MONERO_VANITY_WALLET_PASSWORD` or asked for (twice).
+	#[arg(long, value_name = "DIR", conflicts_with_all = ["calculate_split_key", "subaddress", "integrated"])]
+	wallet_dir: Option<std::path::PathBuf>,
+
+	/// How to print: human readable text, 1 JSON object when done, or 1 JSON event per line
+	///
+	/// Errors are printed as an `error` event (to stdout) in the JSON modes.
+	#[arg(long, value_enum, default_value_t = Output::default(), global = true)]
+	output: Output,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+	/// Decrypt and print a file written by `--encrypt` (or `--checkpoint`)
+	Decrypt {
+		/// The encrypted file
+		file: std::path::PathBuf,
+
+		/// Also print the worker seeds of a checkpoint
+		///
+		/// They are private key material, so they are left out by default.
+		#[arg(long)]
+		show_seeds: bool,
+	},
+	/// Run headless, controlled by a local HTTP/JSON-RPC API
+	///
+	/// `POST /start`, `POST /stop`, `GET /status`, `GET /results` and `POST /json_rpc`.
+	/// Every request needs `Authorization: Bearer TOKEN`, keep it on localhost (or behind an SSH tunnel).
+	Serve {
+		/// The address to listen on
+		#[arg(long, value_name = "ADDR", default_value = crate::daemon::LISTEN)]
+		listen: String,
+
+		/// The bearer token every request must have
+		///
+		/// A random one is generated and printed if not given.
+		#[arg(long, value_name = "TOKEN")]
+		token: Option<String>,
+	},
+	/// Write a split key job file to hand to whoever calculates it
+	///
+	/// They run `--calculate-split-key FILE` and send back `FILE.result.json`,
+	/// which is joined with `--join-split-key FILE.result.json <EVERY_PRIVATE_SPLIT_KEY_PART> --split-job FILE`.
+	SplitJob {
+		/// The public split key part of every contributor
+		#[arg(long, required = true, num_args(1..), value_name = "PUBLIC_PART")]
+		public: Vec<String>,
+		/// Address regex pattern to look for
+		#[arg(long, short)]
+		pattern: String,
+		/// Start from 1st character instead of: ^..PATTERN.*$
+		#[arg(long, short)]
+		first: bool,
+		/// How many addresses to find
+		#[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+		count: u64,
+		/// Which Monero network to generate addresses for
+		#[arg(long, short, value_enum, default_value_t = Network::default())]
+		network: Network,
+		/// How to reach you, e.g. an email (free text)
+		#[arg(long, default_value = "")]
+		contact: String,
+		/// Join the found wallets with a random private view key instead of deriving it from the spend key
+		#[arg(long)]
+		random_view_key: bool,
+		/// Where to write the job file
+		file: std::path::PathBuf,
+	},
+}
+
+// The chances of a search, estimated while its workers already run.
+struct Estimate {
+	chance: Option<Chance>,
+	// How many times more likely with `--ignore-case`/`--leet`.
+	likelier: Option<f64>,
+	suffix: Option<Chance>,
 }
 
 impl Cli {
 	//-------------------------------------------------- CLI argument handling
 	#[inline(always)]
 	pub fn handle_args() {
-		let cli = Self::parse();
+		let cli = usage(Self::try_parse());
+		match &cli.command {
+			Some(Command::Decrypt { file, show_seeds }) => Self::decrypt(file, *show_seeds, cli.output),
+			Some(Command::Serve { listen, token }) => Self::serve(listen, token.clone(), cli.output),
+			Some(Command::SplitJob { public, pattern, first, count, network, contact, random_view_key, file }) => {
+				let pattern_type = match *first {
+					true  => PatternType::First,
+					false => PatternType::Third,
+				};
+				let job = crate::split::JobFile::new(public.clone(), pattern.clone(), pattern_type, *count, *network, contact.clone(), *random_view_key);
+				Self::split_job(&job, file, cli.output)
+			},
+			None => (),
+		}
+
+		// `--resume` continues with the original arguments, but prints the way it was asked to.
+		let (mut cli, args, resume) = match &cli.resume {
+			Some(path) => {
+				let checkpoint = crate::vault::password(Purpose::Checkpoint)
+					.and_then(|password| Checkpoint::load(path, &password));
+				match checkpoint {
+					Ok((checkpoint, vault)) => {
+						let output = cli.output;
+						let mut cli = usage(Self::try_parse_from(&checkpoint.args));
+						cli.checkpoint = Some(path.clone());
+						cli.output = output;
+						(cli, checkpoint.args.clone(), Some((checkpoint, vault)))
+					},
+					Err(e) => cli.output.fail(ExitCode::Checkpoint, format!("Checkpoint file [{}]: {e}", path.display())),
+				}
+			},
+			None => (cli, std::env::args().collect(), None),
+		};
+		let out = cli.output;
+
 		if cli.gen_private_split_key {
-			Self::gen_private_split_key()
+			Self::gen_private_split_key(cli.network, out)
+		}
+		if let Some(seed) = &cli.check_seed {
+			Self::check_seed(seed, cli.network, out)
 		}
-		if let Some(keys) = cli.join_split_key {
-			Self::join_split_key(keys)
+
+		// `--leet` substitution table.
+		let leet = match (&cli.leet_file, cli.leet) {
+			(Some(path), _) => match std::fs::read_to_string(path).map(|table| Leet::from_table(&table)) {
+				Ok(Ok(leet)) => Some(leet),
+				Ok(Err(e))   => out.fail(ExitCode::File, format!("Leet file [{}]: {e}", path.display())),
+				Err(e)       => out.fail(ExitCode::File, format!("Could not read leet file [{}]: {e}", path.display())),
+			},
+			(None, true)  => Some(Leet::default()),
+			(None, false) => None,
+		};
+
+		if let Some(keys) = &cli.join_split_key {
+			Self::join_split_key(&cli, keys, leet.as_ref(), out)
 		}
 
-		// Test for `pattern` validity.
-		if cli.pattern.is_empty() {
-			eprintln!("ERROR: Address pattern is empty");
-			exit(1);
-		} else if cli.pattern.contains('I') {
-			eprintln!("ERROR: Address pattern must not contain 'I'");
-			exit(2);
-		} else if cli.pattern.contains('O') {
-			eprintln!("ERROR: Address pattern must not contain 'O'");
-			exit(3);
-		} else if cli.pattern.contains('l') {
-			eprintln!("ERROR: Address pattern must not contain 'l'");
-			exit(4);
-		} else if cli.pattern.contains('0') {
-			eprintln!("ERROR: Address pattern must not contain '0'");
-			exit(5);
-		} else if cli.pattern.contains('+') {
-			eprintln!("ERROR: Address pattern must not contain '+'");
-			exit(6);
-		} else if cli.pattern.contains('/') {
-			eprintln!("ERROR: Address pattern must not contain '/'");
-			exit(7);
+		// `--calculate-split-key JOB_FILE` takes the search from the
+		// job file and writes every result to its result file.
+		let split_results = match cli.calculate_split_key.as_deref() {
+			Some([file]) if !crate::split::is_key(file) => {
+				if !cli.pattern.is_empty() || cli.pattern_file.is_some() || cli.count.is_some() || cli.infinite || cli.once.is_some() || cli.ignore_case || cli.leet || cli.leet_file.is_some() {
+					out.fail(ExitCode::Usage, "A split key job file has its own pattern and count, they can't be given as options");
+				}
+				let path = std::path::PathBuf::from(file);
+				let job = match crate::split::JobFile::read(&path) {
+					Ok(job) => job,
+					Err(e) => out.fail(split_code(&e), format!("Job file [{}]: {e}", path.display())),
+				};
+				let result = crate::split::result_path(&path);
+				let results = match crate::split::ResultFile::open(&result, &job) {
+					Ok(results) => results,
+					Err(e) => out.fail(split_code(&e), format!("Result file [{}]: {e}", result.display())),
+				};
+				// An existing result file is continued.
+				let left = job.count.saturating_sub(results.results.len() as u64);
+				if left == 0 {
+					out.fail(ExitCode::File, format!("Result file [{}] already has all [{}] results, send it back", result.display(), job.count));
+				}
+				cli.pattern             = vec![job.pattern.clone()];
+				cli.first               = job.pattern_type == PatternType::First;
+				cli.network             = job.network;
+				cli.count               = Some(left);
+				cli.calculate_split_key = Some(job.publics.clone());
+				Some((result, results))
+			},
+			_ => None,
+		};
+
+		// `--job` + `--queue` run their own loop.
+		if !cli.job.is_empty() || cli.queue.is_some() {
+			let jobs = Self::jobs(&cli, leet.as_ref(), out);
+			let queue = Queue::new(jobs, threads(cli.threads), cli.schedule, std::time::Duration::from_secs(cli.slice), cli.random_view_key);
+			Self::queue_loop(queue, cli.refresh, out);
 		}
-		let pattern_string = match cli.first {
-			true  => cli.pattern,
-			false => format!("^..{}.*$", cli.pattern),
+
+		// Patterns from `--pattern` + `--pattern-file`.
+		let mut patterns = cli.pattern;
+		if let Some(path) = &cli.pattern_file {
+			match std::fs::read_to_string(path) {
+				Ok(file) => patterns.extend(
+					file.lines()
+						.map(str::trim)
+						.filter(|line| !line.is_empty() && !line.starts_with('#'))
+						.map(String::from)
+				),
+				Err(e) => out.fail(ExitCode::File, format!("Could not read pattern file [{}]: {e}", path.display())),
+			}
+		}
+		if patterns.is_empty() {
+			out.fail(ExitCode::Pattern, "Address pattern is empty");
+		}
+
+		// `--ignore-case` and `--leet` expand characters before validation,
+		// since `l`, `I` and `O` have valid base58 versions.
+		let expanded = cli.ignore_case || leet.is_some();
+		let alternatives = |c| crate::leet::alternatives(leet.as_ref(), c, cli.ignore_case);
+		let plain = patterns.clone();
+
+		// Which patterns stop after their 1st hit.
+		let once: Vec<bool> = match &cli.once {
+			None => Vec::new(),
+			Some(only) if only.is_empty() => vec![true; plain.len()],
+			Some(only) => {
+				if let Some(missing) = only.iter().find(|o| !plain.contains(o)) {
+					out.fail(ExitCode::Usage, format!("--once [{missing}] is not one of the patterns"));
+				}
+				plain.iter().map(|p| only.contains(p)).collect()
+			},
 		};
-		let pattern = match Regex::new(&pattern_string) {
-			Ok(p) => p,
-			Err(e) => { eprintln!("ERROR: Regex failed to build: {}", e); exit(8); },
+		if expanded {
+			patterns = patterns.iter().map(|p| crate::regexes::expand(p, alternatives)).collect();
+		}
+
+		// `--integrated` and `--suffix` match the last 18 characters.
+		let tail = cli.integrated.is_some() || cli.suffix;
+		let wrap = |pattern: &String| match (cli.first, tail) {
+			(true, _)      => pattern.clone(),
+			(false, true)  => cli.tail.regex(pattern),
+			(false, false) => format!("^..{pattern}.*$"),
 		};
+		let regexes: Vec<String> = patterns.iter().map(wrap).collect();
+		let pattern_string = regexes.join(" | ");
 
-		let split_key = cli.calculate_split_key.map(|key|
-			match monero::PublicKey::from_str(&key) {
-				Ok(key) => key.point.decompress().expect("monero-rs decompresses public keys so all `PublicKey`s will be valid points"),
-				Err(e) => { eprintln!("ERROR: Public key entered is not a valid point: {}", e); exit(9); }
+		let split_key = cli.calculate_split_key.map(|keys|
+			match crate::split::publics(&keys) {
+				Ok(publics) => publics,
+				Err(e) => out.fail(split_code(&e), e),
 			}
 		);
 
-		// Test for `thread` validity.
-		let threads = {
-			// Use half if `0`.
-			if cli.threads == 0 {
-				eprintln!("[0] threads selected, defaulting to 50% of available threads: [{}]", *THREADS_HALF);
-				*THREADS_HALF
-			// Use max if over.
-			} else if cli.threads > *THREADS_MAX {
-				eprintln!(
-					"[{}] threads selected, but only [{}] threads detected. Using [{}] threads.",
-					cli.threads,
-					*THREADS_MAX,
-					*THREADS_MAX
-				);
-				*THREADS_MAX
-			// Else, use user input.
-			} else {
-				cli.threads
+		let subaddress = cli.subaddress.map(|keys| {
+			let view = match monero::PrivateKey::from_str(&keys[0]) {
+				Ok(key) => key,
+				Err(e) => out.fail(ExitCode::Key, format!("Private view key entered is not a valid scalar: {e}")),
+			};
+			let spend = match monero::PublicKey::from_str(&keys[1]) {
+				Ok(key) => key.point.decompress().expect("monero-rs decompresses public keys so all `PublicKey`s will be valid points"),
+				Err(e) => out.fail(ExitCode::Key, format!("Public spend key entered is not a valid point: {e}")),
+			};
+			(view, spend)
+		});
+
+		// The 2nd phase of a prefix + suffix search.
+		let suffix_pattern = cli.suffix_pattern.map(|suffix| {
+			let suffix = match expanded {
+				true  => crate::regexes::expand(&suffix, alternatives),
+				false => suffix,
+			};
+			let regex = match cli.first {
+				true  => suffix.clone(),
+				false => cli.tail.regex(&suffix),
+			};
+			if let Err(e) = crate::regexes::validate_pattern(&suffix, &regex, Target::Tail) {
+				out.fail(pattern_code(&e), format!("Suffix pattern: {e}"));
+			}
+			(Patterns::new([&regex]).expect("validated above"), regex)
+		});
+
+		// Integrated addresses use the network of the address given.
+		let mut network = cli.network;
+		let integrated = cli.integrated.map(|address| {
+			let address = match monero::Address::from_str(&address) {
+				Ok(address) => address,
+				Err(e) => out.fail(ExitCode::Key, format!("Address entered is not a valid Monero address: {e}")),
+			};
+			if address.addr_type != monero::AddressType::Standard {
+				out.fail(ExitCode::Key, format!("Address entered must be a standard address, not a {}", address.addr_type));
+			}
+			network = Network::from(address.network);
+			(address.public_spend, address.public_view)
+		});
+
+		// The chance a single try matches.
+		let target = match (tail, &subaddress) {
+			(true, _)        => Target::Tail,
+			(false, Some(_)) => Target::Prefix(network.subaddress_byte()),
+			(false, None)    => Target::Prefix(network.byte()),
+		};
+
+		// Test for `pattern` validity, and reject patterns
+		// that need a character where it can never be.
+		for (pattern, regex) in patterns.iter().zip(&regexes) {
+			if let Err(e) = crate::regexes::validate_pattern(pattern, regex, target) {
+				out.fail(pattern_code(&e), e);
 			}
+		}
+		let pattern = match Patterns::new(&regexes) {
+			Ok(p) => p,
+			Err(e) => out.fail(ExitCode::Pattern, format!("Regex failed to build: {e}")),
+		};
+		let place = crate::difficulty::place(target);
+		// Sampling a regex takes a moment, so this runs while the workers already search.
+		let estimate = {
+			let regexes = regexes.clone();
+			let plain: Option<Vec<String>> = expanded.then(|| plain.iter().map(wrap).collect());
+			let suffix = suffix_pattern.as_ref().map(|(_, regex)| regex.clone());
+			crate::difficulty::spawn(move || {
+				let chance = crate::difficulty::chance_any(&regexes, target);
+				if chance.is_some_and(|chance| chance.p == 0.0) {
+					return Err(format!("Address pattern can never match {place}"));
+				}
+				let likelier = plain.and_then(|plain| {
+					crate::difficulty::chance_any(&plain, target)
+						.filter(|plain| plain.p > 0.0)
+						.zip(chance)
+						.map(|(plain, chance)| chance.p / plain.p)
+				});
+				let suffix = suffix.and_then(|regex| crate::difficulty::chance(&regex, Target::Tail));
+				Ok(Estimate { chance, likelier, suffix })
+			})
+		};
+
+		// Resumed workers continue where they were.
+		let threads = match &resume {
+			Some((checkpoint, _)) => checkpoint.positions.len(),
+			None => threads(cli.threads),
 		};
 
+		// The checkpoint file + its key.
+		let saver = cli.checkpoint.map(|path| {
+			let vault = match resume {
+				Some((_, ref vault)) => Ok(vault.clone()),
+				None => crate::vault::new_password(Purpose::Checkpoint).and_then(|password| crate::vault::Vault::new(&password)),
+			};
+			match vault {
+				Ok(vault) => Saver {
+					path,
+					vault,
+					args,
+					interval: std::time::Duration::from_secs(cli.checkpoint_interval),
+					last: std::time::Instant::now(),
+				},
+				Err(e) => out.fail(ExitCode::Checkpoint, format!("Checkpoint: {e}")),
+			}
+		});
+
+		// The encrypted results file.
+		let results = cli.encrypt.map(|path| {
+			// A new file gets a new (confirmed) password.
+			let password = match path.exists() {
+				true  => crate::vault::password(Purpose::Results),
+				false => crate::vault::new_password(Purpose::Results),
+			};
+			let results = password.and_then(|password| Results::open(&path, &password));
+			match results {
+				Ok(results) => results,
+				Err(e) => out.fail(ExitCode::Results, format!("Results file [{}]: {e}", path.display())),
+			}
+		});
+
+		// Where wallet files are written.
+		let wallets = cli.wallet_dir.map(|dir| {
+			match crate::vault::new_password(Purpose::Wallet) {
+				Ok(password) => WalletDir::new(dir, &password),
+				Err(e) => out.fail(ExitCode::Wallet, format!("Wallet directory [{}]: {e}", dir.display())),
+			}
+		});
+
 		// Make `State`.
-		let state = State {
+		let mut state = State {
 			threads,
 			pattern,
 			pattern_string,
 			split_key,
+			random_view: cli.random_view_key,
+			network,
+			subaddress,
+			major: cli.major,
+			random_index: cli.random_index,
+			integrated,
+			suffix: cli.suffix.then(crate::address::rand_priv),
+			suffix_pattern,
+			once,
+			count: match (cli.infinite, cli.count, cli.once.is_some()) {
+				(true, _, _)          => None,
+				(false, Some(c), _)   => Some(c),
+				(false, None, true)   => None,
+				(false, None, false)  => Some(1),
+			},
+			ignore_case: cli.ignore_case,
+			leet: leet.is_some(),
+			tail_type: cli.tail,
+			output: out,
+			positions: crate::checkpoint::positions(threads, resume.as_ref().map(|(c, _)| c.positions.as_slice())),
 			..Default::default()
 		};
 
+		// Keep the stats of the previous runs.
+		if let Some((checkpoint, _)) = &resume {
+			state.iter  = Arc::new(AtomicU64::new(checkpoint.tries));
+			state.found = checkpoint.found;
+			for (done, resumed) in state.pattern.done.iter().zip(&checkpoint.done) {
+				done.store(*resumed, std::sync::atomic::Ordering::SeqCst);
+			}
+		}
+		let elapsed = resume.as_ref().map_or(0.0, |(c, _)| c.elapsed);
+
 		// Continue to loop.
-		Self::cli_loop(state, cli.refresh);
+		Self::cli_loop(state, cli.refresh, estimate, saver, elapsed, results, wallets, split_results);
 	}
 
-	fn gen_private_split_key() {
+	fn gen_private_split_key(network: Network, out: Output) {
 		let (private_part, public_part) = crate::address::calculate_part_split_key();
+		if out.is_json() {
+			out.event(&Event::SplitKey { private: private_part, public: public_part });
+			ExitCode::Ok.exit();
+		}
 		let mut output = String::new();
 		output += &format!("Private Split Key (keep hidden)   | {private_part}\n");
 		output += &format!("Public Split Key (give this out)  | {public_part}\n\n");
-		output += &format!("Generate the other part with: ./monero-vanity --calculate-split-key {public_part} --pattern <PATTERN_YOU_WANT>");
+		output += &format!("Generate the other part with: ./monero-vanity --calculate-split-key {public_part} [OTHER_PUBLIC_PARTS...]{} --pattern <PATTERN_YOU_WANT>\n", network_arg(network));
+		output += &format!("Or hand out a job file:       ./monero-vanity split-job --public {public_part} [OTHER_PUBLIC_PARTS...]{} --pattern <PATTERN_YOU_WANT> <FILE>", network_arg(network));
+		successful_exit(&output)
+	}
+
+	fn split_job(job: &crate::split::JobFile, file: &std::path::Path, out: Output) {
+		if let Err(e) = crate::split::publics(&job.publics) {
+			out.fail(split_code(&e), e);
+		}
+		let regex = match crate::regexes::validate_pattern(&job.pattern, &job.regex(), Target::Prefix(job.network.byte())) {
+			Ok((_, regex)) => regex,
+			Err(e) => out.fail(pattern_code(&e), e),
+		};
+		if let Err(e) = job.write(file) {
+			out.fail(split_code(&e), e);
+		}
+		if out.is_json() {
+			out.event(&Event::SplitJob { file: file.to_path_buf(), checksum: job.checksum.clone() });
+			ExitCode::Ok.exit();
+		}
+		let mut output = String::new();
+		output += &format!("Job File                   | {}\n", file.display());
+		output += &format!("Checksum                   | {}\n", job.checksum);
+		output += &format!("Pattern                    | {regex}\n");
+		output += &format!("Count                      | {}\n\n", job.count);
+		output += &format!("Calculate it with: ./monero-vanity --calculate-split-key {}\n", file.display());
+		output += &format!("Then join with:    ./monero-vanity --join-split-key {} <EVERY_PRIVATE_SPLIT_KEY_PART> --split-job {}", crate::split::result_path(file).display(), file.display());
 		successful_exit(&output)
 	}
 
-	fn join_split_key(keys: Vec<String>) {
-		let keys: Vec<monero::PrivateKey> = keys.iter().map(|key|
-			match monero::PrivateKey::from_str(&key) {
+	fn join_split_key(cli: &Self, keys: &[String], leet: Option<&Leet>, out: Output) {
+		let privates: Vec<monero::PrivateKey> = keys[1..].iter().map(|key|
+			match monero::PrivateKey::from_str(key) {
 				Ok(key) => key,
-				Err(e) => { eprintln!("ERROR: Private key part entered is not a valid scalar: {e}"); exit(10); }
+				Err(e) => out.fail(ExitCode::Key, format!("Private key part entered is not a valid scalar: {e}")),
 			}
 		).collect();
-		let m = crate::address::join_split_key(keys[0], keys[1]);
 
+		// The requester's own job, never the one in a result file.
+		let job = cli.split_job.as_ref().map(|path|
+			match crate::split::JobFile::read(path) {
+				Ok(job) => job,
+				Err(e) => out.fail(split_code(&e), format!("Job file [{}]: {e}", path.display())),
+			}
+		);
+
+		// A result file has every calculated part of a job.
+		let path = std::path::Path::new(&keys[0]);
+		let (job, proofs) = match path.is_file() {
+			true => {
+				let Some(job) = job else {
+					out.fail(ExitCode::Usage, "Joining a result file needs your own job file (`--split-job FILE`), every result is checked against it");
+				};
+				let file = match crate::split::ResultFile::read(path) {
+					Ok(file) if file.results.is_empty() => out.fail(ExitCode::File, format!("Result file [{}] has no results yet", path.display())),
+					Ok(file) => file,
+					Err(e) => out.fail(split_code(&e), format!("Result file [{}]: {e}", path.display())),
+				};
+				if file.job != job {
+					out.fail(ExitCode::Key, format!("Result file [{}]: {}", path.display(), crate::split::Error::Mismatch("it was written for another job file")));
+				}
+				for proof in &file.results {
+					if let Err(e) = job.check(proof) {
+						out.fail(split_code(&e), format!("Result file [{}]: {e}", path.display()));
+					}
+				}
+				(job, file.results)
+			},
+			false => match crate::split::Proof::from_blob(&keys[0]) {
+				Ok(proof) => (job.unwrap_or_else(|| Self::own_job(cli, &privates, leet, out)), vec![proof]),
+				Err(e) => out.fail(ExitCode::Key, e),
+			},
+		};
+
+		// The view key is derived as agreed in the job.
+		let random_view = job.random_view;
 		let mut output = String::new();
-		output += &format!("Monero Address             | {}\n", m.0);
-		output += &format!("Private Spend Key          | {}\n", m.1);
-		output += &format!("Private View Key           | {}\n\n", m.2);
-		output += &format!("Recover with: ./monero-wallet-cli --generate-from-spend-key <YOUR_WALLET_NAME>");
+		for proof in &proofs {
+			let m = match proof.join(&job, &privates, random_view) {
+				Ok(m) => m,
+				Err(e) => out.fail(split_code(&e), e),
+			};
+			let network = proof.network;
+			let seed = (!random_view).then(|| crate::mnemonic::encode_str(&m.1, Language::English)).flatten();
+
+			if out.is_json() {
+				out.event(&Event::Result(Box::new(Found { address: m.0, spend: Some(m.1), view: Some(m.2), seed, network, ..Default::default() })));
+				continue;
+			}
+			output += &format!("Pattern                    | {}\n", job.regex());
+			output += &format!("Contributors               | {}\n", proof.publics.len());
+			output += &format!("Helper Tries               | {}\n", Unsigned::from(proof.tries));
+			output += &format!("Monero Address             | {}\n", m.0);
+			output += &format!("Private Spend Key          | {}\n", m.1);
+			output += &format!("Private View Key           | {}\n", m.2);
+			if let Some(seed) = seed {
+				output += &format!("Seed                       | {seed}\n");
+			}
+			output += "\n";
+		}
+		if out.is_json() {
+			ExitCode::Ok.exit();
+		}
+		output += &recover_with(random_view, proofs[0].network);
 		successful_exit(&output)
+	}
 
+	// The job a `vanity...` blob must be for: the requester's own pattern,
+	// never the one the helper claims, for the public keys of `privates`.
+	fn own_job(cli: &Self, privates: &[monero::PrivateKey], leet: Option<&Leet>, out: Output) -> crate::split::JobFile {
+		let [pattern] = cli.pattern.as_slice() else {
+			out.fail(ExitCode::Usage, "Joining needs the 1 pattern you asked for (`--pattern`) or your job file (`--split-job`), the joined address is checked against it");
+		};
+		let pattern = match cli.ignore_case || leet.is_some() {
+			true  => crate::regexes::expand(pattern, |c| crate::leet::alternatives(leet, c, cli.ignore_case)),
+			false => pattern.clone(),
+		};
+		let pattern_type = match cli.first {
+			true  => PatternType::First,
+			false => PatternType::Third,
+		};
+		let publics = privates.iter().map(crate::split::part).collect();
+		let job = crate::split::JobFile::new(publics, pattern, pattern_type, 1, cli.network, String::new(), cli.random_view_key);
+		if let Err(e) = crate::regexes::validate_pattern(&job.pattern, &job.regex(), Target::Prefix(job.network.byte())) {
+			out.fail(pattern_code(&e), e);
+		}
+		job
+	}
+
+	fn decrypt(file: &std::path::Path, show_seeds: bool, out: Output) {
+		// The file is either `--encrypt` results or a checkpoint,
+		// use whichever of their passwords opens it.
+		let text = [Purpose::Results, Purpose::Checkpoint]
+			.into_iter()
+			.filter_map(|purpose| std::env::var(purpose.env()).ok())
+			.find_map(|password| crate::results::decrypt(file, &password).ok())
+			.map_or_else(
+				|| crate::vault::ask("Password: ").and_then(|password| crate::results::decrypt(file, &password)),
+				Ok,
+			);
+		// Checkpoint seeds are only printed if asked for.
+		let text = text.map(|text| match serde_json::from_str::<crate::checkpoint::Checkpoint>(&text) {
+			Ok(checkpoint) if !show_seeds => checkpoint.redacted(),
+			_ => text,
+		});
+		match text {
+			Ok(text) if out.is_json() => out.event(&Event::Decrypt { text }),
+			Ok(text) => print!("{text}"),
+			Err(e) => out.fail(ExitCode::Results, format!("[{}]: {e}", file.display())),
+		}
+		ExitCode::Ok.exit()
+	}
+
+	fn serve(listen: &str, token: Option<String>, out: Output) {
+		let server = match tiny_http::Server::http(listen) {
+			Ok(server) => server,
+			Err(e) => out.fail(ExitCode::Serve, format!("Could not listen on [{listen}]: {e}")),
+		};
+		let addr = server.server_addr().to_ip();
+		if addr.is_some_and(|addr| !addr.ip().is_loopback()) {
+			eprintln!("WARNING: [{listen}] is not a local address, anyone who can reach it can control the search and read private keys");
+		}
+		let address = addr.map_or(listen.to_string(), |a| a.to_string());
+		let token = match token {
+			Some(token) if token.trim().is_empty() => out.fail(ExitCode::Usage, "--token must not be empty"),
+			Some(token) => token.trim().to_string(),
+			None => crate::daemon::token(),
+		};
+		match out.is_json() {
+			true  => out.event(&Event::Listen { address, token: token.clone() }),
+			false => println!("Listening on http://{address}\nToken | {token}"),
+		}
+		crate::daemon::Daemon::new(token).serve(&server);
+		ExitCode::Ok.exit()
+	}
+
+	fn check_seed(seed: &str, network: Network, out: Output) {
+		let spend = match crate::mnemonic::decode(seed, Language::English) {
+			Ok(spend) => spend,
+			Err(e) => out.fail(ExitCode::Seed, e),
+		};
+		let view = crate::address::view_from_spend(&spend);
+		let address = monero::Address::from_keypair(network.monero(), &monero::KeyPair { view, spend });
+		let seed = crate::mnemonic::encode(&spend, Language::English);
+
+		if out.is_json() {
+			out.event(&Event::Result(Box::new(Found {
+				address: address.to_string(),
+				spend: Some(spend.to_string()),
+				view: Some(view.to_string()),
+				seed: Some(seed),
+				network,
+				..Default::default()
+			})));
+			ExitCode::Ok.exit();
+		}
+		let mut output = String::new();
+		output += &format!("Monero Address             | {address}\n");
+		output += &format!("Private Spend Key          | {spend}\n");
+		output += &format!("Private View Key           | {view}\n");
+		output += &format!("Seed                       | {seed}");
+		successful_exit(&output)
 	}
 
 	//-------------------------------------------------- CLI loop.
-	fn cli_loop(mut state: State, refresh: u64) {
+	#[allow(clippy::too_many_arguments)]
+	fn cli_loop(
+		mut state: State,
+		refresh: u64,
+		estimate: std::thread::JoinHandle<Result<Estimate, String>>,
+		mut saver: Option<Saver>,
+		elapsed: f64,
+		mut results: Option<Results>,
+		wallets: Option<WalletDir>,
+		mut split_results: Option<(std::path::PathBuf, crate::split::ResultFile)>,
+	) {
 		// Create channels to/from workers.
-		let (to, from) = std::sync::mpsc::channel::<(String, String, String)>();
+		let (to, mut from) = std::sync::mpsc::channel::<Hit>();
 
-		// Set timer.
-		state.start = std::time::Instant::now();
+		// Set timer, a resumed search started `elapsed` seconds ago.
+		let now = std::time::Instant::now();
+		state.start = now.checked_sub(std::time::Duration::from_secs_f64(elapsed)).unwrap_or(now);
 
 		// Spawn workers.
-		crate::address::spawn_workers(
-			state.threads,
-			&to,
-			&state.iter,
-			&state.die,
-			&state.pattern,
-			state.split_key,
-		);
+		if let Some(spend) = state.suffix {
+			crate::address::spawn_suffix_workers(
+				state.threads,
+				&to,
+				&state.iter,
+				&state.die,
+				&state.positions,
+				&state.pattern,
+				spend,
+				state.network,
+			);
+		} else if let Some((spend, view)) = state.integrated {
+			crate::address::spawn_integrated_workers(
+				state.threads,
+				&to,
+				&state.iter,
+				&state.die,
+				&state.positions,
+				&state.pattern,
+				spend,
+				view,
+				state.network,
+			);
+		} else if let Some((view, spend)) = state.subaddress {
+			crate::address::spawn_subaddress_workers(
+				state.threads,
+				&to,
+				&state.iter,
+				&state.die,
+				&state.positions,
+				&state.pattern,
+				view,
+				spend,
+				state.major,
+				state.random_index,
+				state.network,
+			);
+		} else {
+			crate::address::spawn_workers(
+				state.threads,
+				&to,
+				&state.iter,
+				&state.die,
+				&state.positions,
+				&state.pattern,
+				state.split_key.as_deref().map(crate::split::sum),
+				state.random_view,
+				state.network,
+			);
+		}
+		// Only workers hold a sender now, so the
+		// channel disconnects once they all returned.
+		drop(to);
 
-		println!(
-			"Threads | {}\nRefresh | {}ms\nPattern | {}\n",
-			state.threads,
-			refresh,
-			state.pattern_string,
-		);
+		let out = state.output;
+		let suffix_chance = match estimate.join().expect("estimating never panics") {
+			Ok(estimate) => {
+				state.chance   = estimate.chance;
+				state.likelier = estimate.likelier;
+				estimate.suffix
+			},
+			Err(e) => out.fail(ExitCode::Impossible, e),
+		};
+		if out.is_json() {
+			out.stream(&Event::Start {
+				threads: state.threads,
+				network: state.network,
+				patterns: state.pattern.set.patterns().to_vec(),
+				count: state.count,
+				chance: state.chance.map(|c| c.p),
+			});
+		} else {
+			println!(
+				"Threads | {}\nRefresh | {}ms\nNetwork | {}\nPattern | {}",
+				state.threads,
+				refresh,
+				state.network,
+				state.pattern_string,
+			);
+			if let Some((_, suffix)) = &state.suffix_pattern {
+				println!("Suffix  | {suffix}");
+			}
+			match state.count {
+				Some(1)     => (),
+				Some(count) => println!("Count   | {count}"),
+				None if !state.once.is_empty() && state.once.iter().all(|o| *o) => println!("Count   | Every pattern once"),
+				None if !state.once.is_empty() => println!("Count   | Infinite, --once patterns only once"),
+				None        => println!("Count   | Infinite"),
+			}
+			if state.ignore_case {
+				println!("Case    | Ignored");
+			}
+			if state.leet {
+				println!("Leet    | On");
+			}
+			match (state.chance, state.likelier) {
+				(Some(chance), Some(likelier)) => println!("Chance  | {chance} (~{likelier:.1}x more likely)"),
+				(Some(chance), None)           => println!("Chance  | {chance}"),
+				(None, _)                      => println!("Chance  | Unknown (no random address matched, it may be impossible)"),
+			}
+			if let Some(chance) = suffix_chance {
+				println!("Suffix  | Chance {chance}");
+			}
+			if state.integrated.is_some() || state.suffix.is_some() || state.suffix_pattern.is_some() {
+				println!("Tail    | {}", state.tail_type);
+			}
+			if let Some(saver) = &saver {
+				println!("Save    | {} (every {}s)", saver.path.display(), saver.interval.as_secs());
+			}
+			if let Some(results) = &results {
+				println!("Results | {} (encrypted)", results.path().display());
+			}
+			if let Some(wallets) = &wallets {
+				println!("Wallets | {}", wallets.path().display());
+			}
+			if let Some((path, _)) = &split_results {
+				println!("Split   | {}", path.display());
+			}
+			println!();
+		}
+		let mut found = Vec::new();
+
+		// The suffix search tries view keys.
+		let mut keys = match state.suffix {
+			Some(_) => "view keys",
+			None    => "keys",
+		};
 
 		// Loop, printing stats and checking for msg every 1 second.
+		let interrupted = out.interrupted();
 		loop {
 			let iter = state.iter.load(std::sync::atomic::Ordering::SeqCst);
 
-			if let Ok(m) = from.try_recv() {
+			// `Json` mode prints what was found so far when stopped.
+			if interrupted.load(std::sync::atomic::Ordering::SeqCst) {
+				state.die.store(true, std::sync::atomic::Ordering::SeqCst);
+				out.event(&Event::Done {
+					tries: iter,
+					speed: crate::speed::calculate(&state.start, iter),
+					elapsed: state.start.elapsed().as_secs_f64(),
+					found: state.found,
+					results: Some(found),
+					interrupted: true,
+				});
+				ExitCode::Ok.exit();
+			}
+
+			loop {
+				let hit = match from.try_recv() {
+					Ok(hit) => hit,
+					Err(std::sync::mpsc::TryRecvError::Empty) => break,
+					// Only subaddress workers return by themselves, once every index was walked.
+					Err(std::sync::mpsc::TryRecvError::Disconnected) if state.subaddress.is_some() => {
+						fail_line(out);
+						out.fail(ExitCode::Impossible, format!("Every subaddress index of account [{}] was searched, found [{}] matching", state.major, state.found));
+					},
+					// Any other worker only stops by panicking.
+					Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+						fail_line(out);
+						out.fail(ExitCode::Internal, format!("Every worker thread stopped unexpectedly, found [{}]", state.found));
+					},
+				};
+
+				// Phase 1 (prefix) is done, keep its spend key
+				// and continue into phase 2 (suffix) on view keys.
+				if let Some((pattern, pattern_string)) = state.suffix_pattern.take() {
+					let Hit::Wallet { spend, .. } = hit else { unreachable!("prefix workers find wallets") };
+					let elapsed = state.start.elapsed();
+					state.phase_one = Some((spend, iter, elapsed));
+					if out.is_json() {
+						out.stream(&Event::Prefix { address: hit.address().to_string(), tries: iter, elapsed: elapsed.as_secs_f64() });
+					} else {
+						println!(
+							"\n\nPrefix found | {}\nTries        | {}\nElapsed      | {}\n\nSearching view keys for suffix: {pattern_string}\n",
+							hit.address(),
+							Unsigned::from(iter),
+							Time::from(&elapsed),
+						);
+					}
+
+					// The old workers may still be finishing
+					// their batch, so give phase 2 its own signals.
+					state.die.store(true, std::sync::atomic::Ordering::SeqCst);
+					let (to, new_from) = std::sync::mpsc::channel::<Hit>();
+					from                 = new_from;
+					state.iter           = Arc::new(AtomicU64::new(0));
+					state.die            = Arc::new(AtomicBool::new(false));
+					state.suffix         = Some(spend);
+					state.pattern        = pattern;
+					state.pattern_string = pattern_string;
+					state.start          = std::time::Instant::now();
+					state.chance         = suffix_chance;
+					keys                 = "view keys";
+					crate::address::spawn_suffix_workers(
+						state.threads,
+						&to,
+						&state.iter,
+						&state.die,
+						&crate::checkpoint::positions(state.threads, None),
+						&state.pattern,
+						spend,
+						state.network,
+					);
+					continue;
+				}
+
+				// With `--once`, the pattern is skipped from now on, the others are still searched.
+				// Other threads may have found the same pattern before being told to skip it.
+				if state.once.get(hit.pattern()).copied().unwrap_or(false) && !state.pattern.finish(hit.pattern()) {
+					continue;
+				}
+
+				// A split key part is sent back with what it found.
+				let blob = match (&state.split_key, &hit) {
+					(Some(publics), Hit::Wallet { spend, .. }) => {
+						let proof = crate::split::Proof::new(spend, publics, &state.pattern.set.patterns()[hit.pattern()], iter, state.network);
+						let blob = proof.to_blob();
+						if let Some((path, file)) = split_results.as_mut() {
+							if let Err(e) = file.add(proof.clone(), path) {
+								fail_line(out);
+								out.fail(split_code(&e), format!("Result file [{}]: {e}", path.display()));
+							}
+						}
+						Some((proof.address, blob))
+					},
+					_ => None,
+				};
+
 				let mut output = String::new();
-				output +=     &format!("Tries                     | {} \n", Unsigned::from(iter));
-				output +=     &format!("Speed                     | {} keys per second\n", Unsigned::from(crate::speed::calculate(&state.start, iter)));
-				output +=     &format!("Elapsed                   | {}\n", Time::from(&state.start.elapsed()));
-				if state.split_key.is_some() {
-					output += &format!("Calculated Split Key part | {}\n\n", m.1);
-					output += &format!("Join keys with: ./monero-vanity --join-split-key {} <PRIVATE_SPLIT_KEY_PART>", m.1);
+				if state.pattern.set.len() > 1 {
+					output += &format!("Pattern                   | {}\n", state.pattern.set.patterns()[hit.pattern()]);
+				}
+				let phase = match state.phase_one {
+					Some((_, tries, elapsed)) => {
+						output += &format!("Prefix Tries              | {}\n", Unsigned::from(tries));
+						output += &format!("Prefix Elapsed            | {}\n", Time::from(&elapsed));
+						"Suffix "
+					},
+					None => "",
+				};
+				output +=     &format!("{:<26}| {} \n", format!("{phase}Tries"), Unsigned::from(iter));
+				output +=     &format!("{:<26}| {} {keys} per second\n", format!("{phase}Speed"), Unsigned::from(crate::speed::calculate(&state.start, iter)));
+				output +=     &format!("{:<26}| {}\n", format!("{phase}Elapsed"), Time::from(&state.start.elapsed()));
+				let stats = output.clone();
+				match (&hit, &blob) {
+					(Hit::Integrated { address, payment_id, .. }, _) => {
+						output += &format!("Integrated Address        | {address}\n");
+						output += &format!("Payment ID                | {payment_id}\n\n");
+						output += "Payments sent to this address go to your existing wallet and show the payment ID above.";
+					},
+					(Hit::Subaddress { address, major, minor, .. }, _) => {
+						output += &format!("Monero Subaddress         | {address}\n");
+						output += &format!("Subaddress Index          | {major}/{minor}\n\n");
+						output += &format!("Create with: `address new` in account {major} until index {minor}, or `address one-off {major} {minor}` in ./monero-wallet-cli");
+					},
+					(Hit::Wallet { .. }, Some((address, blob))) => {
+						output += &format!("Monero Address            | {address}...\n");
+						output += &format!("Calculated Split Key part | {blob}\n\n");
+						match &split_results {
+							Some((path, _)) => output += &format!("Send [{}] back, join keys with: ./monero-vanity --join-split-key {} <EVERY_PRIVATE_SPLIT_KEY_PART> --split-job <YOUR_JOB_FILE>", path.display(), path.display()),
+							None => output += &format!("Send the part back, join keys with: ./monero-vanity --join-split-key {blob} <EVERY_PRIVATE_SPLIT_KEY_PART> --pattern <YOUR_PATTERN>{}", network_arg(state.network)),
+						}
+					},
+					(Hit::Wallet { address, spend, view, .. }, None) => {
+						output += &format!("Monero Address            | {address}\n");
+						output += &format!("Private Spend Key         | {spend}\n");
+						output += &format!("Private View Key          | {view}\n");
+						if state.suffix.is_none() && !state.random_view {
+							output += &format!("Seed                      | {}\n", crate::mnemonic::encode(spend, Language::English));
+						}
+						output += "\n";
+						output += &recover_with(state.suffix.is_some() || state.random_view, state.network);
+					},
+				}
+
+				// With `--wallet-dir`, the wallet can be opened as-is.
+				// A failed write is only reported once the keys are printed/saved.
+				let (wallet, wallet_error) = match (&wallets, &hit) {
+					(Some(wallets), Hit::Wallet { address, spend, view, .. }) => match wallets.write(address, spend, view, state.network) {
+						Ok(path) => (Some(path), None),
+						Err(e) => (None, Some(format!("Wallet directory [{}]: {e}", wallets.path().display()))),
+					},
+					_ => (None, None),
+				};
+				if let Some(wallet) = &wallet {
+					output += &format!("\nOr open:      {}", open_with(wallet, state.network));
+				}
+
+				// With `--encrypt`, keys only go to the file
+				// and the terminal only sees the public address.
+				if let Some(results) = results.as_mut() {
+					if let Err(e) = results.add(&output) {
+						fail_line(out);
+						rescue(&output);
+						out.fail(ExitCode::Results, format!("Results file [{}]: {e}", results.path().display()));
+					}
+					output = stats;
+					match (&hit, &blob) {
+						(Hit::Integrated { address, .. }, _) => output += &format!("Integrated Address        | {address}\n"),
+						(Hit::Subaddress { address, .. }, _) => output += &format!("Monero Subaddress         | {address}\n"),
+						(Hit::Wallet { .. }, Some((address, _))) => output += &format!("Monero Address            | {address}...\n"),
+						(Hit::Wallet { address, .. }, None)  => output += &format!("Monero Address            | {address}\n"),
+					}
+					if let Some(wallet) = &wallet {
+						output += &format!("Wallet File               | {}\n", wallet.display());
+					}
+					output += &format!("Saved to                  | {}", results.path().display());
+				}
+
+				// Stream every match, stop after `count`.
+				state.found += 1;
+				if out.is_json() {
+					let saved_to = results.as_ref().map(|r| r.path().to_path_buf());
+					let mut result = Found::new(&state, &hit, iter, wallet, saved_to.clone());
+					if let Some((address, blob)) = blob {
+						result.address   = address;
+						result.split_key = saved_to.is_none().then_some(blob);
+					}
+					out.stream(&Event::Result(Box::new(result.clone())));
+					found.push(result);
 				} else {
-					output += &format!("Monero Address            | {}\n", m.0);
-					output += &format!("Private Spend Key         | {}\n", m.1);
-					output += &format!("Private View Key          | {}\n\n", m.2);
-					output += &format!("Recover with: ./monero-wallet-cli --generate-from-spend-key <YOUR_WALLET_NAME>");
+					print!("{}[2K\r", 27 as char);
+					print_result(&output);
+				}
+				if let Some(e) = wallet_error {
+					// `json` only prints results when done.
+					if out == Output::Json && results.is_none() {
+						rescue(&output);
+					}
+					fail_line(out);
+					out.fail(ExitCode::Wallet, e);
+				}
+				if state.count == Some(state.found) || (!state.once.is_empty() && state.pattern.all_done()) {
+					state.die.store(true, std::sync::atomic::Ordering::SeqCst);
+					out.event(&Event::Done {
+						tries: iter,
+						speed: crate::speed::calculate(&state.start, iter),
+						elapsed: state.start.elapsed().as_secs_f64(),
+						found: state.found,
+						results: (out == Output::Json).then_some(found),
+						interrupted: false,
+					});
+					ExitCode::Ok.exit();
 				}
-				successful_exit(&output)
 			}
 
+			// Save where the workers are.
+			if let Some(saver) = saver.as_mut().filter(|s| s.due()) {
+				let done = state.pattern.done.iter().map(|d| d.load(std::sync::atomic::Ordering::SeqCst)).collect();
+				if let Err(e) = saver.save(iter, state.start.elapsed(), state.found, done, &state.positions) {
+					eprintln!("\nWARNING: Checkpoint [{}]: {e}", saver.path.display());
+				}
+			}
+
+			let speed = crate::speed::calculate(&state.start, iter);
+			if out.is_json() {
+				out.stream(&Event::Progress { tries: iter, speed, elapsed: state.start.elapsed().as_secs_f64(), found: state.found });
+				std::thread::sleep(std::time::Duration::from_millis(refresh));
+				continue;
+			}
 			print!(
-				"{}[2K\rTries: [{}] | Speed: [{} keys per second] | Elapsed: [{}]",
+				"{}[2K\rTries: [{}] | Speed: [{} {keys} per second] | Elapsed: [{}]",
 				27 as char,
 				Unsigned::from(iter),
-				Unsigned::from(crate::speed::calculate(&state.start, iter)),
+				Unsigned::from(speed),
 				Time::from(&state.start.elapsed()),
 			);
+			if state.count != Some(1) {
+				print!(" | Found: [{}]", state.found);
+			}
+			if let Some(chance) = state.chance {
+				if let Some(etas) = chance.etas(speed) {
+					print!(" | 50/90/99%: [{etas}]");
+				}
+				print!(" | Found by now: [{:.1}%]", chance.found_by(iter) * 100.0);
+			}
 			std::io::stdout().lock().flush();
 
 			std::thread::sleep(std::time::Duration::from_millis(refresh));
 		}
 	}
+
+	//-------------------------------------------------- Queue loop.
+	// The validated jobs of `--job` + `--queue`.
+	fn jobs(cli: &Self, leet: Option<&Leet>, out: Output) -> Vec<Job> {
+		let mut lines = cli.job.clone();
+		if let Some(path) = &cli.queue {
+			match std::fs::read_to_string(path) {
+				Ok(file) => lines.extend(
+					file.lines()
+						.map(str::trim)
+						.filter(|line| !line.is_empty() && !line.starts_with('#'))
+						.map(String::from)
+				),
+				Err(e) => out.fail(ExitCode::File, format!("Could not read queue file [{}]: {e}", path.display())),
+			}
+		}
+		if lines.is_empty() {
+			out.fail(ExitCode::Pattern, "Job queue is empty");
+		}
+
+		let alternatives = |c| crate::leet::alternatives(leet, c, cli.ignore_case);
+		lines.iter().map(|line| {
+			let spec: Spec = match line.parse() {
+				Ok(spec) => spec,
+				Err(e) => out.fail(ExitCode::Usage, format!("Job [{line}]: {e}")),
+			};
+			let expanded = match cli.ignore_case || leet.is_some() {
+				true  => crate::regexes::expand(&spec.pattern, alternatives),
+				false => spec.pattern.clone(),
+			};
+			let regex = match (cli.first, spec.mode) {
+				(true, _)                 => expanded.clone(),
+				(false, Mode::Integrated) => cli.tail.regex(&expanded),
+				(false, Mode::Standard)   => format!("^..{expanded}.*$"),
+			};
+			let network = spec.network.unwrap_or(cli.network);
+			match Job::new(&spec.pattern, &expanded, regex, spec.mode, spec.address.as_deref(), spec.count, network) {
+				Ok(job) => job,
+				Err(e) => {
+					let code = match e {
+						JobError::Pattern(_)    => ExitCode::Pattern,
+						JobError::Impossible(_) => ExitCode::Impossible,
+						JobError::Address(_)    => ExitCode::Key,
+					};
+					out.fail(code, format!("Job [{line}]: {e}"))
+				},
+			}
+		}).collect()
+	}
+
+	fn queue_loop(mut queue: Queue, refresh: u64, out: Output) {
+		// The 1st job already searches while the chances are estimated.
+		let mut updates = queue.poll();
+		queue.estimated(true);
+
+		if out.is_json() {
+			out.stream(&Event::Start {
+				threads: queue.threads,
+				network: queue.jobs[0].job.network,
+				patterns: queue.jobs.iter().map(|e| e.job.regex.clone()).collect(),
+				count: Some(queue.jobs.iter().map(|e| e.job.count).sum()),
+				chance: None,
+			});
+		} else {
+			println!("Threads  | {}\nRefresh  | {}ms\nSchedule | {}", queue.threads, refresh, queue.schedule);
+			if queue.schedule == Schedule::RoundRobin {
+				println!("Slice    | {}s", queue.slice.as_secs());
+			}
+			for (i, entry) in queue.jobs.iter().enumerate() {
+				let chance = entry.chance.map_or("Unknown".to_string(), |c| c.to_string());
+				println!("Job {:<4} | {} | {} | {} | Count {} | Chance {chance}", i + 1, entry.job.regex, entry.job.mode, entry.job.network, entry.job.count);
+			}
+			println!();
+		}
+		let mut found = Vec::new();
+
+		let interrupted = out.interrupted();
+		loop {
+			// `Json` mode prints what was found so far when stopped.
+			if interrupted.load(std::sync::atomic::Ordering::SeqCst) {
+				let tries = queue.tries();
+				let elapsed = queue.start.elapsed();
+				queue.stop();
+				out.event(&Event::Done {
+					tries,
+					speed: (tries as f64 / elapsed.as_secs_f64()) as u64,
+					elapsed: elapsed.as_secs_f64(),
+					found: queue.jobs.iter().map(|e| e.found).sum(),
+					results: Some(found),
+					interrupted: true,
+				});
+				ExitCode::Ok.exit();
+			}
+
+			for update in updates {
+				match update {
+					Update::Switch(i) => if !out.is_json() {
+						println!("{}[2K\rJob {} | {}", 27 as char, i + 1, queue.jobs[i].job.regex);
+					},
+					Update::Found(i, hit) => {
+						let entry = &queue.jobs[i];
+						let tries = entry.iter.load(std::sync::atomic::Ordering::SeqCst);
+						if out.is_json() {
+							let mut result = Found {
+								address: hit.address().to_string(),
+								pattern: Some(entry.job.regex.clone()),
+								job:     Some(i + 1),
+								network: entry.job.network,
+								tries:   Some(tries),
+								elapsed: Some(queue.elapsed(i).as_secs_f64()),
+								..Default::default()
+							};
+							match &hit {
+								Hit::Wallet { spend, view, .. } => {
+									result.spend = Some(spend.to_string());
+									result.view  = Some(view.to_string());
+									result.seed  = (!queue.random_view).then(|| crate::mnemonic::encode(spend, Language::English));
+								},
+								Hit::Subaddress { major, minor, .. } => result.index = Some([*major, *minor]),
+								Hit::Integrated { payment_id, .. }   => result.payment_id = Some(payment_id.clone()),
+							}
+							out.stream(&Event::Result(Box::new(result.clone())));
+							found.push(result);
+							continue;
+						}
+
+						let mut output = String::new();
+						output += &format!("Job                       | {} ({})\n", i + 1, entry.job.regex);
+						output += &format!("Tries                     | {}\n", Unsigned::from(tries));
+						output += &format!("Speed                     | {} keys per second\n", Unsigned::from(queue.speed(i)));
+						output += &format!("Elapsed                   | {}\n", Time::from(&queue.elapsed(i)));
+						match &hit {
+							Hit::Wallet { address, spend, view, .. } => {
+								output += &format!("Monero Address            | {address}\n");
+								output += &format!("Private Spend Key         | {spend}\n");
+								output += &format!("Private View Key          | {view}\n");
+								if !queue.random_view {
+									output += &format!("Seed                      | {}\n", crate::mnemonic::encode(spend, Language::English));
+								}
+								output += "\n";
+								output += &recover_with(queue.random_view, entry.job.network);
+							},
+							Hit::Subaddress { address, major, minor, .. } => {
+								output += &format!("Monero Subaddress         | {address}\n");
+								output += &format!("Subaddress Index          | {major}/{minor}\n");
+							},
+							Hit::Integrated { address, payment_id, .. } => {
+								output += &format!("Integrated Address        | {address}\n");
+								output += &format!("Payment ID                | {payment_id}\n\n");
+								output += "Payments sent to this address go to your existing wallet and show the payment ID above.";
+							},
+						}
+						print!("{}[2K\r", 27 as char);
+						print_result(&output);
+					},
+					Update::Done(i) => {
+						let entry = &queue.jobs[i];
+						out.stream(&Event::Job {
+							job: i + 1,
+							pattern: entry.job.regex.clone(),
+							network: entry.job.network,
+							tries: entry.iter.load(std::sync::atomic::Ordering::SeqCst),
+							speed: queue.speed(i),
+							elapsed: queue.elapsed(i).as_secs_f64(),
+							found: entry.found,
+						});
+						if !out.is_json() {
+							println!("{}[2K\r{}", 27 as char, queue.stats(i));
+						}
+					},
+					Update::Finished => {
+						let tries = queue.tries();
+						let elapsed = queue.start.elapsed();
+						if !out.is_json() {
+							println!("\nEvery job is done\n");
+							for i in 0..queue.jobs.len() {
+								println!("{}", queue.stats(i));
+							}
+						}
+						out.event(&Event::Done {
+							tries,
+							speed: (tries as f64 / elapsed.as_secs_f64()) as u64,
+							elapsed: elapsed.as_secs_f64(),
+							found: queue.jobs.iter().map(|e| e.found).sum(),
+							results: (out == Output::Json).then_some(found),
+							interrupted: false,
+						});
+						ExitCode::Ok.exit();
+					},
+				}
+			}
+
+			// The stats of the job being searched.
+			if let Some(i) = queue.current {
+				let entry = &queue.jobs[i];
+				let tries = entry.iter.load(std::sync::atomic::Ordering::SeqCst);
+				if out.is_json() {
+					out.stream(&Event::Progress { tries, speed: queue.speed(i), elapsed: queue.elapsed(i).as_secs_f64(), found: entry.found });
+				} else {
+					print!(
+						"{}[2K\rJob: [{}/{}] | Tries: [{}] | Speed: [{} keys per second] | Elapsed: [{}] | Found: [{}/{}]",
+						27 as char,
+						i + 1,
+						queue.jobs.len(),
+						Unsigned::from(tries),
+						Unsigned::from(queue.speed(i)),
+						Time::from(&queue.elapsed(i)),
+						entry.found,
+						entry.job.count,
+					);
+					std::io::stdout().lock().flush();
+				}
+			}
+
+			std::thread::sleep(std::time::Duration::from_millis(refresh));
+			updates = queue.poll();
+		}
+	}
 }
 
-fn successful_exit(output: &str) {
+// The exit code of a rejected address pattern.
+fn pattern_code(e: &crate::regexes::PatternError) -> ExitCode {
+	match e {
+		crate::regexes::PatternError::Invalid(_)    => ExitCode::Pattern,
+		crate::regexes::PatternError::Impossible(_) => ExitCode::Impossible,
+	}
+}
+
+// The exit code of a split key error.
+fn split_code(e: &crate::split::Error) -> ExitCode {
+	use crate::split::Error::*;
+	match e {
+		Possession(_) | NonCanonical(_) | Identity(_) | SmallOrder(_) | Torsion(_) | Duplicate(_) | Cancelled => ExitCode::WeakKey,
+		Format(_) | Corrupted | Invalid(_) | Public(_) | Missing(_) | Spend | Address | Pattern(_) | Mismatch(_) => ExitCode::Key,
+		File(_) | Version(_) | Checksum => ExitCode::File,
+	}
+}
+
+// Test for `thread` validity.
+fn threads(threads: usize) -> usize {
+	// Use half if `0`.
+	if threads == 0 {
+		eprintln!("[0] threads selected, defaulting to 50% of available threads: [{}]", *THREADS_HALF);
+		*THREADS_HALF
+	// Use max if over.
+	} else if threads > *THREADS_MAX {
+		eprintln!(
+			"[{}] threads selected, but only [{}] threads detected. Using [{}] threads.",
+			threads,
+			*THREADS_MAX,
+			*THREADS_MAX
+		);
+		*THREADS_MAX
+	// Else, use user input.
+	} else {
+		threads
+	}
+}
+
+// The `--network` argument to pass along in example commands.
+fn network_arg(network: Network) -> String {
+	match network {
+		Network::Mainnet => String::new(),
+		_ => format!(" --network {}", network.to_string().to_lowercase()),
+	}
+}
+
+// The network flag of `monero-wallet-cli`.
+fn wallet_cli_network(network: Network) -> &'static str {
+	match network {
+		Network::Mainnet  => "",
+		Network::Stagenet => " --stagenet",
+		Network::Testnet  => " --testnet",
+	}
+}
+
+// How to restore the wallet, depending on the network and if the view key was derived.
+fn recover_with(random_view: bool, network: Network) -> String {
+	let network = wallet_cli_network(network);
+	match random_view {
+		true  => format!("Recover with: ./monero-wallet-cli{network} --generate-from-keys <YOUR_WALLET_NAME>"),
+		false => format!("Recover with: ./monero-wallet-cli{network} --generate-from-spend-key <YOUR_WALLET_NAME>"),
+	}
+}
+
+fn open_with(wallet: &std::path::Path, network: Network) -> String {
+	format!("./monero-wallet-cli{} --wallet-file {}", wallet_cli_network(network), wallet.display())
+}
+
+fn print_result(output: &str) {
 	println!("\n@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@");
 	println!("{output}");
 	println!("@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@@");
-	std::process::exit(0);
+}
+
+// Invalid command lines exit with `ExitCode::Usage`, `--help`/`--version` with `0`.
+fn usage(cli: Result<Cli, clap::Error>) -> Cli {
+	cli.unwrap_or_else(|e| {
+		let _ = e.print();
+		match e.use_stderr() {
+			true  => ExitCode::Usage.exit(),
+			false => ExitCode::Ok.exit(),
+		}
+	})
+}
+
+// Move past the progress line before a (text) error.
+fn fail_line(out: Output) {
+	if !out.is_json() {
+		println!();
+	}
+}
+
+// Keys that could not be saved are never dropped,
+// they are printed to stderr before exiting.
+fn rescue(keys: &str) {
+	eprintln!("!!! THESE KEYS COULD NOT BE SAVED, COPY THEM BEFORE CLOSING THIS TERMINAL !!!");
+	eprintln!("{keys}");
+	eprintln!("!!! THESE KEYS COULD NOT BE SAVED, COPY THEM BEFORE CLOSING THIS TERMINAL !!!");
+}
+
+fn successful_exit(output: &str) {
+	print_result(output);
+	ExitCode::Ok.exit()
 }
 
 //---------------------------------------------------------------------------------------------------- TESTS
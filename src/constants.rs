@@ -27,7 +27,13 @@ pub const THIRD: &str =
 r#"monero-vanity automatically prefixes your input
 with `^..` and suffixes it with `.*$` so that
 your PATTERN starts from the 3rd character
-until the 11th character of the address.
+of the address. Patterns can reach until the 44th
+character, the rest is controlled by the view key.
+
+The first 2 characters are (mostly) fixed by the network:
+Mainnet addresses start with `4`,
+Stagenet addresses start with `5`,
+Testnet addresses start with `9` or `A`.
 
 Example input: `hinto`
 Actual regex used: `^..hinto.*$`"#;
@@ -38,9 +44,68 @@ r#"This disables the prefixing + suffixing when using `Third`
 Warning: this puts you in full control of the regex,
 you can input any value, even an impossible one."#;
 
+pub const STANDARD: &str = "Find a new wallet whose address starts with the pattern.";
+
+pub const INTEGRATED: &str =
+r#"Find a payment ID for an existing wallet so that its
+integrated address matches the pattern in the last 18 characters.
+
+The keys stay fixed, enter the wallet's standard address.
+The network is taken from the address."#;
+
+pub const SUFFIX: &str =
+r#"The pattern must be at the end of the integrated address.
+
+Example input: `hinto`
+Actual regex used: `hinto$`"#;
+
+pub const INFIX: &str =
+r#"The pattern can be anywhere in the last 18 characters of the integrated address.
+
+Example input: `hinto`
+Actual regex used: `hinto`"#;
+
+pub const NETWORK: &str = "Which Monero network to generate addresses for. Stagenet/Testnet are useful for rehearsing before using Mainnet.";
+
+pub const COUNT: &str = "How many addresses to find before stopping. With `Infinite`, every address found is added to the history until `Stop` is clicked.";
+
+pub const IGNORE_CASE: &str = "Match every base58 version of each letter, e.g. `hinto` also finds `HiNTo`. Letters base58 doesn't have are skipped: `l` only matches `L`, `o` only matches `o`.";
+
+pub const LEET: &str = "Also match leetspeak/lookalikes of each character, e.g. `hello` also finds `h3LLo` or `he11o`: `h[e3][L1][L1]o`. Base58 has no `0`, `O`, `I` or `l`, so only substitutes that exist are used.";
+
+pub const RESULTS: &str = "Found private spend/view keys and seeds are only written to this password-encrypted file (read it with `monero-vanity decrypt`), the history just shows the address. `Show keys` puts them in the history instead, which is not saved anywhere.";
+
+pub const QUEUE: &str = "Add the current pattern, mode, address, network and count as a job. Queued jobs share the same threads, the settings above are kept per job.";
+
+pub const SCHEDULE: &str =
+r#"How the threads move through the queue.
+
+Sequential: each job runs until it found its count, then the next one starts.
+Round-robin: every unfinished job searches for a time slice in turn."#;
+
+pub const SLICE: &str = "How many seconds each job searches before the next job takes over (round-robin).";
+
+pub const SEARCH: &str = "Find a new wallet or an integrated address of an existing wallet.";
+
+pub const SPLIT_KEY: &str =
+r#"Find a vanity address with the help of others, without anyone
+(including whoever searches) knowing the final private key alone.
+
+1. Every contributor generates a split key part and gives out the public part
+2. Whoever searches pastes every public part and starts the search
+3. The calculated part they send back is joined with every private part"#;
+
+pub const SPLIT_GENERATE: &str = "Generate a new private + public split key part. Give the public part out, keep the private part secret until joining.";
+
+pub const SPLIT_SEARCH: &str = "Search for an address that adds the public part of every contributor, with the pattern and settings of the Search tab. The calculated part found can be sent back as-is.";
+
+pub const SPLIT_JOB: &str = "The job agreed with whoever searches: the public parts above with the pattern, count and network of the Search tab. Write it before handing out the public parts, joining is checked against it and derives the view key as it says.";
+
+pub const SPLIT_JOIN: &str = "Join the calculated part with the private part of every contributor. It is only joined if it was calculated for the public parts and network of the job file and exactly these private parts, and the address matches the pattern of the job file (what the helper claims is not trusted).";
+
 pub const STATS: & str = "Stats on the current/previous run.";
 
-pub const HISTORY: & str = "The found addresses, and private spend/view keys.";
+pub const HISTORY: & str = "The found addresses, payment IDs, where their keys were saved (or the private spend/view keys and 25-word seeds with `Show keys`), and the stats of each queued job.";
 
 //---------------------------------------------------------------------------------------------------- `egui`
 /// `egui` Colors.
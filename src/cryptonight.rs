@@ -0,0 +1,288 @@
+//---------------------------------------------------------------------------------------------------- Use
+// The original `CryptoNight` (variant 0) slow hash.
+//
+// `monero-wallet-cli` derives the key of a `.keys` file from
+// the password with this, so it is needed to write wallet files.
+//
+// This follows `src/crypto/slow-hash.c` (portable version), the
+// `AES` round and the `BLAKE-256`, `Groestl-256`, `JH-256` and
+// `Skein-512-256` final step come from crates.
+
+//---------------------------------------------------------------------------------------------------- Constants
+// 2MB scratchpad.
+const MEMORY: usize = 1 << 21;
+// Main loop iterations (2 per loop).
+const ITER: usize = 1 << 20;
+// `AES` block size.
+const BLOCK: usize = 16;
+// How many bytes of the `Keccak` state fill the scratchpad.
+const INIT: usize = 128;
+// `Keccak` rate with a 200 byte output (`keccak1600`).
+const RATE: usize = 136;
+
+//---------------------------------------------------------------------------------------------------- CryptoNight
+/// `cn_slow_hash(data, variant 0)`.
+///
+/// This takes ~1 second (2MB of memory, 1 million iterations).
+pub fn slow_hash(data: &[u8]) -> [u8; 32] {
+	let mut state = keccak1600(data);
+	let mut bytes = to_bytes(&state);
+
+	// Fill the scratchpad by encrypting the `Keccak` state.
+	let keys = expand_key(&bytes[..32]);
+	let mut text = [0_u8; INIT];
+	text.copy_from_slice(&bytes[64..64 + INIT]);
+	let mut scratchpad = vec![0_u8; MEMORY];
+	for chunk in scratchpad.chunks_exact_mut(INIT) {
+		for block in text.chunks_exact_mut(BLOCK) {
+			pseudo_round(block, &keys);
+		}
+		chunk.copy_from_slice(&text);
+	}
+
+	// Memory-hard loop.
+	let mut a = [0_u8; BLOCK];
+	let mut b = [0_u8; BLOCK];
+	for i in 0..BLOCK {
+		a[i] = bytes[i] ^ bytes[32 + i];
+		b[i] = bytes[16 + i] ^ bytes[48 + i];
+	}
+	for _ in 0..ITER / 2 {
+		// Iteration 1: AES round.
+		let j = index(&a);
+		let mut c = read(&scratchpad, j);
+		aes_round(&mut c, &a);
+		write(&mut scratchpad, j, &xor(&c, &b));
+
+		// Iteration 2: 64x64 multiplication.
+		let j = index(&c);
+		let d = read(&scratchpad, j);
+		let product = (half(&c, 0) as u128) * (half(&d, 0) as u128);
+		let mut sum = [0_u8; BLOCK];
+		sum[..8].copy_from_slice(&half(&a, 0).wrapping_add((product >> 64) as u64).to_le_bytes());
+		sum[8..].copy_from_slice(&half(&a, 1).wrapping_add(product as u64).to_le_bytes());
+		write(&mut scratchpad, j, &sum);
+		a = xor(&sum, &d);
+		b = c;
+	}
+
+	// Fold the scratchpad back into the `Keccak` state.
+	let keys = expand_key(&bytes[32..64]);
+	text.copy_from_slice(&bytes[64..64 + INIT]);
+	for chunk in scratchpad.chunks_exact(INIT) {
+		for (block, memory) in text.chunks_exact_mut(BLOCK).zip(chunk.chunks_exact(BLOCK)) {
+			block.iter_mut().zip(memory).for_each(|(t, m)| *t ^= m);
+			pseudo_round(block, &keys);
+		}
+	}
+	bytes[64..64 + INIT].copy_from_slice(&text);
+	for (word, chunk) in state.iter_mut().zip(bytes.chunks_exact(8)) {
+		*word = u64::from_le_bytes(chunk.try_into().unwrap());
+	}
+	keccak::f1600(&mut state);
+	let bytes = to_bytes(&state);
+
+	// The final hash is picked by the state.
+	match bytes[0] & 3 {
+		0 => blake256(&bytes),
+		1 => groestl256(&bytes),
+		2 => jh256(&bytes),
+		_ => skein512_256(&bytes),
+	}
+}
+
+// The scratchpad offset a block points to.
+fn index(block: &[u8; BLOCK]) -> usize {
+	(half(block, 0) as usize) & (MEMORY - BLOCK)
+}
+
+fn half(block: &[u8; BLOCK], i: usize) -> u64 {
+	u64::from_le_bytes(block[i * 8..i * 8 + 8].try_into().unwrap())
+}
+
+fn read(scratchpad: &[u8], j: usize) -> [u8; BLOCK] {
+	scratchpad[j..j + BLOCK].try_into().unwrap()
+}
+
+fn write(scratchpad: &mut [u8], j: usize, block: &[u8; BLOCK]) {
+	scratchpad[j..j + BLOCK].copy_from_slice(block);
+}
+
+fn xor(a: &[u8; BLOCK], b: &[u8; BLOCK]) -> [u8; BLOCK] {
+	std::array::from_fn(|i| a[i] ^ b[i])
+}
+
+//---------------------------------------------------------------------------------------------------- Keccak
+// `Keccak` with the original `0x01` padding, returning the whole state.
+fn keccak1600(data: &[u8]) -> [u64; 25] {
+	let mut state = [0_u64; 25];
+	let mut blocks = data.chunks_exact(RATE);
+	for block in &mut blocks {
+		absorb(&mut state, block);
+		keccak::f1600(&mut state);
+	}
+
+	let rest = blocks.remainder();
+	let mut last = [0_u8; RATE];
+	last[..rest.len()].copy_from_slice(rest);
+	last[rest.len()] = 0x01;
+	last[RATE - 1] |= 0x80;
+	absorb(&mut state, &last);
+	keccak::f1600(&mut state);
+	state
+}
+
+fn absorb(state: &mut [u64; 25], block: &[u8]) {
+	for (word, chunk) in state.iter_mut().zip(block.chunks_exact(8)) {
+		*word ^= u64::from_le_bytes(chunk.try_into().unwrap());
+	}
+}
+
+fn to_bytes(state: &[u64; 25]) -> [u8; 200] {
+	let mut bytes = [0_u8; 200];
+	for (chunk, word) in bytes.chunks_exact_mut(8).zip(state) {
+		chunk.copy_from_slice(&word.to_le_bytes());
+	}
+	bytes
+}
+
+//---------------------------------------------------------------------------------------------------- AES
+// `x * 2` in `GF(2^8)`.
+const fn xtime(x: u8) -> u8 {
+	(x << 1) ^ (((x >> 7) & 1) * 0x1b)
+}
+
+// `a * b` in `GF(2^8)`.
+const fn gmul(mut a: u8, mut b: u8) -> u8 {
+	let mut p = 0;
+	while b != 0 {
+		if b & 1 != 0 {
+			p ^= a;
+		}
+		a = xtime(a);
+		b >>= 1;
+	}
+	p
+}
+
+// The `AES` S-box: the affine transform of the inverse (`x^254`),
+// the crate doesn't expose the key schedule `CryptoNight` needs.
+const SBOX: [u8; 256] = {
+	let mut sbox = [0_u8; 256];
+	let mut i = 0;
+	while i < 256 {
+		let (mut inv, mut base, mut e) = (1_u8, i as u8, 254_u8);
+		while e != 0 {
+			if e & 1 != 0 {
+				inv = gmul(inv, base);
+			}
+			base = gmul(base, base);
+			e >>= 1;
+		}
+		sbox[i] = inv ^ inv.rotate_left(1) ^ inv.rotate_left(2) ^ inv.rotate_left(3) ^ inv.rotate_left(4) ^ 0x63;
+		i += 1;
+	}
+	sbox
+};
+
+// 1 full `AES` round (`aesenc`).
+fn aes_round(block: &mut [u8], key: &[u8]) {
+	aes::hazmat::cipher_round(aes::Block::from_mut_slice(block), aes::Block::from_slice(key));
+}
+
+// 10 full rounds, without the first/last round differences.
+fn pseudo_round(block: &mut [u8], keys: &[[u8; BLOCK]; 10]) {
+	for key in keys {
+		aes_round(block, key);
+	}
+}
+
+// The first 10 round keys of `AES-256`.
+fn expand_key(key: &[u8]) -> [[u8; BLOCK]; 10] {
+	let mut words = [[0_u8; 4]; 40];
+	for (i, word) in words.iter_mut().take(8).enumerate() {
+		word.copy_from_slice(&key[i * 4..i * 4 + 4]);
+	}
+
+	let mut rcon = 1;
+	for i in 8..40 {
+		let mut temp = words[i - 1];
+		if i % 8 == 0 {
+			temp = [SBOX[temp[1] as usize] ^ rcon, SBOX[temp[2] as usize], SBOX[temp[3] as usize], SBOX[temp[0] as usize]];
+			rcon = xtime(rcon);
+		} else if i % 8 == 4 {
+			temp = temp.map(|b| SBOX[b as usize]);
+		}
+		words[i] = std::array::from_fn(|j| words[i - 8][j] ^ temp[j]);
+	}
+
+	std::array::from_fn(|k| std::array::from_fn(|i| words[k * 4 + i / 4][i % 4]))
+}
+
+//---------------------------------------------------------------------------------------------------- Final hashes
+// The SHA-3 finalists are taken from their crates as-is.
+fn blake256(data: &[u8]) -> [u8; 32] {
+	<blake_hash::Blake256 as blake_hash::Digest>::digest(data).into()
+}
+
+fn groestl256(data: &[u8]) -> [u8; 32] {
+	<groestl::Groestl256 as groestl::Digest>::digest(data).into()
+}
+
+fn jh256(data: &[u8]) -> [u8; 32] {
+	<jh::Jh256 as jh::Digest>::digest(data).into()
+}
+
+fn skein512_256(data: &[u8]) -> [u8; 32] {
+	<skein::Skein512<skein::consts::U32> as skein::Digest>::digest(data).into()
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn hex(bytes: &[u8]) -> String {
+		bytes.iter().map(|b| format!("{b:02x}")).collect()
+	}
+
+	#[test]
+	fn aes_sbox() {
+		assert_eq!(SBOX[0x00], 0x63);
+		assert_eq!(SBOX[0x01], 0x7c);
+		assert_eq!(SBOX[0x53], 0xed);
+		assert_eq!(SBOX[0xff], 0x16);
+	}
+
+	#[test]
+	// `AES-256` key expansion of `00..1f`.
+	fn aes_key_expansion() {
+		let key: Vec<u8> = (0..32).collect();
+		let keys = expand_key(&key);
+		assert_eq!(hex(&keys[2]), "a573c29fa176c498a97fce93a572c09c");
+		assert_eq!(hex(&keys[9]), "45f5a66017b2d387300d4d33640a820a");
+	}
+
+	#[test]
+	fn finalist_hashes() {
+		assert_eq!(hex(&blake256(b"")),        "716f6e863f744b9ac22c97ec7b76ea5f5908bc5b2f67c61510bfc4751384ea7a");
+		assert_eq!(hex(&blake256(&[0])),       "0ce8d4ef4dd7cd8d62dfded9d4edb0a774ae6a41929a74da23109e8f11139c87");
+		assert_eq!(hex(&blake256(&[0; 72])),   "d419bad32d504fb7d44d460c42c5593fe544fa4c135dec31e21bd9abdcc22d41");
+		assert_eq!(hex(&groestl256(b"")),      "1a52d11d550039be16107f9c58db9ebcc417f16f736adb2502567119f0083467");
+		assert_eq!(hex(&jh256(b"")),           "46e64619c18bb0a92a5e87185a47eef83ca747b8fcc8e1412921357e326df434");
+		assert_eq!(hex(&skein512_256(b"")),    "39ccc4554a8b31853b9de7a1fe638a24cce6b35a55f2431009e18780335d2621");
+	}
+
+	#[test]
+	// `tests/hash/tests-slow.txt` in the Monero repo.
+	fn slow_hash_vectors() {
+		for (data, hash) in [
+			(&b"de omnibus dubitandum"[..],      "2f8e3df40bd11f9ac90c743ca8e32bb391da4fb98612aa3b6cdc639ee00b31f5"),
+			(&b"abundans cautela non nocet"[..], "722fa8ccd594d40e4a41f3822734304c8d5eff7e1b528408e2229da38ba553c4"),
+			(&b"caveat emptor"[..],              "bbec2cacf69866a8e740380fe7b818fc78f8571221742d729d9d02d7f8989b87"),
+			(&b"ex nihilo nihil fit"[..],        "b1257de4efc5ce28c6b40ceb1c6c8f812a64634eb3e81c5220bee9b2b76a6f05"),
+		] {
+			assert_eq!(hex(&slow_hash(data)), hash);
+		}
+	}
+}
@@ -0,0 +1,595 @@
+//---------------------------------------------------------------------------------------------------- Use
+use crate::difficulty::Target;
+use crate::network::Network;
+use crate::hit::Hit;
+use crate::output::Found;
+use crate::regexes::Patterns;
+use crate::state::State;
+use crate::threads::{THREADS_HALF,THREADS_MAX};
+use serde::{Serialize,Deserialize};
+use rand::Rng;
+use serde_json::{json,Value};
+use std::io::Read;
+use std::net::SocketAddr;
+use std::sync::{Arc,Mutex};
+use std::sync::atomic::{AtomicBool,AtomicU64,Ordering};
+use std::time::Duration;
+
+//---------------------------------------------------------------------------------------------------- Constants
+/// The default `serve --listen` address.
+pub const LISTEN: &str = "127.0.0.1:18090";
+
+//---------------------------------------------------------------------------------------------------- Requests
+/// The parameters of `start`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Start {
+	/// The address patterns to look for.
+	pub pattern: Vec<String>,
+	/// Which Monero network to generate addresses for.
+	#[serde(default)]
+	pub network: Network,
+	/// How many threads to use (`0` is half).
+	#[serde(default)]
+	pub threads: usize,
+	/// How many addresses to find (`null` is infinite).
+	#[serde(default = "Start::count")]
+	pub count: Option<u64>,
+	/// Start from the 1st character instead of `^..PATTERN.*$`.
+	#[serde(default)]
+	pub first: bool,
+	/// Match every base58 version of each letter.
+	#[serde(default)]
+	pub ignore_case: bool,
+	/// Also match the default leetspeak/lookalikes of each character.
+	#[serde(default)]
+	pub leet: bool,
+	/// Use a random private view key instead of deriving it.
+	#[serde(default)]
+	pub random_view_key: bool,
+}
+
+impl Start {
+	const fn count() -> Option<u64> {
+		Some(1)
+	}
+}
+
+/// What a request failed with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+	/// The request itself is invalid (HTTP `400`).
+	Invalid(String),
+	/// There is no search (HTTP `404`).
+	NoSearch,
+	/// A search is already running (HTTP `409`).
+	Running,
+	/// There is no such method (HTTP `404`).
+	UnknownMethod(String),
+}
+
+impl Error {
+	fn message(&self) -> String {
+		match self {
+			Self::Invalid(e)       => e.clone(),
+			Self::NoSearch         => "No search was started".into(),
+			Self::Running          => "A search is already running, stop it first".into(),
+			Self::UnknownMethod(m) => format!("Unknown method: {m}"),
+		}
+	}
+
+	const fn http(&self) -> u16 {
+		match self {
+			Self::Invalid(_)       => 400,
+			Self::NoSearch         => 404,
+			Self::Running          => 409,
+			Self::UnknownMethod(_) => 404,
+		}
+	}
+
+	// JSON-RPC 2.0 error codes.
+	const fn rpc(&self) -> i32 {
+		match self {
+			Self::Invalid(_)       => -32602,
+			Self::NoSearch         => -32001,
+			Self::Running          => -32002,
+			Self::UnknownMethod(_) => -32601,
+		}
+	}
+}
+
+//---------------------------------------------------------------------------------------------------- Status
+/// The stats of the current (or last) search.
+#[derive(Clone, Debug, Serialize)]
+pub struct Status {
+	pub running: bool,
+	pub threads: usize,
+	pub network: Network,
+	pub patterns: Vec<String>,
+	/// `null` is infinite.
+	pub count: Option<u64>,
+	/// The chance a single try matches (`null` if unknown).
+	pub chance: Option<f64>,
+	pub tries: u64,
+	/// Tries per second.
+	pub speed: u64,
+	/// Seconds.
+	pub elapsed: f64,
+	pub found: u64,
+}
+
+//---------------------------------------------------------------------------------------------------- Daemon
+// A search the daemon started.
+struct Job {
+	state: State,
+	results: Arc<Mutex<Vec<Found>>>,
+	// How long it ran for, once it stopped.
+	ended: Arc<Mutex<Option<Duration>>>,
+}
+
+/// The largest request body `serve` reads (bytes).
+pub const MAX_BODY: usize = 64 * 1024;
+
+/// A random bearer token for `serve` (64 hex characters).
+pub fn token() -> String {
+	rand::thread_rng().gen::<[u8; 32]>().iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// The headless search controller behind `serve`.
+///
+/// This holds 1 search at a time, its workers report to a
+/// collector thread that keeps the results and stops at `count`.
+pub struct Daemon {
+	job: Mutex<Option<Job>>,
+	/// Every request must have `Authorization: Bearer <token>`.
+	token: String,
+}
+
+impl Daemon {
+	/// A daemon that only answers requests with `token`.
+	pub fn new(token: String) -> Self {
+		Self { job: Mutex::new(None), token }
+	}
+
+	/// Validate and start a new search.
+	pub fn start(&self, start: Start) -> Result<Status, Error> {
+		let mut job = self.job.lock().unwrap();
+		if job.as_ref().is_some_and(|job| !job.state.die.load(Ordering::SeqCst)) {
+			return Err(Error::Running);
+		}
+
+		// Same rules as the CLI/GUI/queue.
+		if start.pattern.is_empty() {
+			return Err(Error::Invalid("Address pattern is empty".into()));
+		}
+		let leet = start.leet.then(crate::leet::Leet::default);
+		let target = Target::Prefix(start.network.byte());
+		let mut regexes = Vec::with_capacity(start.pattern.len());
+		for pattern in &start.pattern {
+			let pattern = match start.ignore_case || leet.is_some() {
+				true  => crate::regexes::expand(pattern, |c| crate::leet::alternatives(leet.as_ref(), c, start.ignore_case)),
+				false => pattern.clone(),
+			};
+			let regex = match start.first {
+				true  => pattern.clone(),
+				false => format!("^..{pattern}.*$"),
+			};
+			let (_, regex) = crate::regexes::validate_pattern(&pattern, &regex, target).map_err(|e| Error::Invalid(e.to_string()))?;
+			regexes.push(regex);
+		}
+		let pattern = Patterns::new(&regexes).map_err(|e| Error::Invalid(e.to_string()))?;
+
+		let threads = match start.threads {
+			0 => *THREADS_HALF,
+			t => t.min(*THREADS_MAX),
+		};
+		let state = State {
+			threads,
+			pattern_string: regexes.join(" | "),
+			pattern,
+			random_view: start.random_view_key,
+			network: start.network,
+			count: start.count,
+			ignore_case: start.ignore_case,
+			leet: start.leet,
+			chance: crate::difficulty::chance_any(&regexes, target),
+			positions: crate::checkpoint::positions(threads, None),
+			iter: Arc::new(AtomicU64::new(0)),
+			die: Arc::new(AtomicBool::new(false)),
+			..Default::default()
+		};
+
+		let (to, from) = std::sync::mpsc::channel();
+		crate::address::spawn_workers(
+			state.threads,
+			&to,
+			&state.iter,
+			&state.die,
+			&state.positions,
+			&state.pattern,
+			None,
+			state.random_view,
+			state.network,
+		);
+
+		let new = Job {
+			state,
+			results: Arc::new(Mutex::new(Vec::new())),
+			ended: Arc::new(Mutex::new(None)),
+		};
+		Self::collect(&new, from);
+		let status = Self::status_of(&new);
+		*job = Some(new);
+		Ok(status)
+	}
+
+	// Keep every result until `count` is reached or the search is stopped.
+	fn collect(job: &Job, from: std::sync::mpsc::Receiver<Hit>) {
+		let state = State {
+			pattern: job.state.pattern.clone(),
+			random_view: job.state.random_view,
+			network: job.state.network,
+			start: job.state.start,
+			..Default::default()
+		};
+		let (iter, die, count) = (job.state.iter.clone(), job.state.die.clone(), job.state.count);
+		let (results, ended) = (job.results.clone(), job.ended.clone());
+
+		std::thread::spawn(move || {
+			// Every worker holds a sender, so this ends when they all die.
+			while let Ok(hit) = from.recv() {
+				if die.load(Ordering::SeqCst) {
+					break;
+				}
+				let mut results = results.lock().unwrap();
+				results.push(Found::new(&state, &hit, iter.load(Ordering::SeqCst), None, None));
+				if count == Some(results.len() as u64) {
+					die.store(true, Ordering::SeqCst);
+					*ended.lock().unwrap() = Some(state.start.elapsed());
+				}
+			}
+		});
+	}
+
+	/// Stop the current search (its results are kept).
+	pub fn stop(&self) -> Result<Status, Error> {
+		let job = self.job.lock().unwrap();
+		let job = job.as_ref().ok_or(Error::NoSearch)?;
+		if !job.state.die.swap(true, Ordering::SeqCst) {
+			*job.ended.lock().unwrap() = Some(job.state.start.elapsed());
+		}
+		Ok(Self::status_of(job))
+	}
+
+	/// The stats of the current (or last) search.
+	pub fn status(&self) -> Result<Status, Error> {
+		self.job.lock().unwrap().as_ref().map(Self::status_of).ok_or(Error::NoSearch)
+	}
+
+	/// Every result of the current (or last) search.
+	pub fn results(&self) -> Result<Vec<Found>, Error> {
+		let job = self.job.lock().unwrap();
+		let job = job.as_ref().ok_or(Error::NoSearch)?;
+		let results = job.results.lock().unwrap().clone();
+		Ok(results)
+	}
+
+	fn status_of(job: &Job) -> Status {
+		let state = &job.state;
+		let tries = state.iter.load(Ordering::SeqCst);
+		let elapsed = job.ended.lock().unwrap().unwrap_or_else(|| state.start.elapsed());
+		Status {
+			running: !state.die.load(Ordering::SeqCst),
+			threads: state.threads,
+			network: state.network,
+			patterns: state.pattern.set.patterns().to_vec(),
+			count: state.count,
+			chance: state.chance.map(|c| c.p),
+			tries,
+			speed: (tries as f64 / elapsed.as_secs_f64()) as u64,
+			elapsed: elapsed.as_secs_f64(),
+			found: job.results.lock().unwrap().len() as u64,
+		}
+	}
+
+	/// Call a method by name (`start`, `stop`, `status`, `results`).
+	pub fn call(&self, method: &str, params: Value) -> Result<Value, Error> {
+		let json = |v: Result<_, Error>| v.map(|v| serde_json::to_value(v).expect("responses always serialize"));
+		match method {
+			"start" => {
+				let start = serde_json::from_value(params).map_err(|e| Error::Invalid(format!("Invalid parameters: {e}")))?;
+				json(self.start(start))
+			},
+			"stop"    => json(self.stop()),
+			"status"  => json(self.status()),
+			"results" => self.results().map(|results| json!({ "results": results })),
+			_ => Err(Error::UnknownMethod(method.into())),
+		}
+	}
+
+	//-------------------------------------------------- HTTP
+	/// Answer HTTP requests on `server` forever.
+	///
+	/// - `POST /start`    -> Start a search, the body is the [`Start`] JSON
+	/// - `POST /stop`     -> Stop the search
+	/// - `GET  /status`   -> [`Status`]
+	/// - `GET  /results`  -> `{ "results": [...] }`
+	/// - `POST /json_rpc` -> JSON-RPC 2.0 with the same methods
+	///
+	/// Every request needs the bearer token and a `Host` of the listen address
+	/// (so other websites can't reach it through the browser), bodies must be JSON
+	/// and at most [`MAX_BODY`] bytes.
+	pub fn serve(&self, server: &tiny_http::Server) {
+		let addr = server.server_addr().to_ip();
+		for mut request in server.incoming_requests() {
+			let mut body = String::new();
+			let (code, response) = match self.check(&request, addr) {
+				Err((code, message)) => (code, json!({ "error": { "code": code, "message": message } })),
+				// A chunked body has no length to check up front.
+				Ok(()) => match request.as_reader().take(MAX_BODY as u64 + 1).read_to_string(&mut body) {
+					Ok(_) if body.len() > MAX_BODY => (413, json!({ "error": { "code": 413, "message": "Request body is too large" } })),
+					Ok(_)  => self.route(request.method(), request.url(), &body),
+					Err(_) => (400, json!({ "error": { "code": 400, "message": "Request body is not UTF-8" } })),
+				},
+			};
+			let mut response = tiny_http::Response::from_string(response.to_string())
+				.with_status_code(code)
+				.with_header(tiny_http::Header::from_bytes("Content-Type", "application/json").expect("valid header"));
+			if code == 401 {
+				response.add_header(tiny_http::Header::from_bytes("WWW-Authenticate", "Bearer").expect("valid header"));
+			}
+			let _ = request.respond(response);
+		}
+	}
+
+	// The `Host`, token and `Content-Type` of a request.
+	fn check(&self, request: &tiny_http::Request, addr: Option<SocketAddr>) -> Result<(), (u16, &'static str)> {
+		let header = |name: &'static str| request.headers()
+			.iter()
+			.find(|h| h.field.equiv(name))
+			.map(|h| h.value.as_str().trim());
+
+		if !header("Host").is_some_and(|host| host_ok(host, addr)) {
+			return Err((403, "Host is not the listen address"));
+		}
+
+		let token = header("Authorization").and_then(|auth| auth.strip_prefix("Bearer ")).unwrap_or_default();
+		if !same(token.trim().as_bytes(), self.token.as_bytes()) {
+			return Err((401, "Missing or wrong bearer token"));
+		}
+
+		let json = header("Content-Type")
+			.and_then(|c| c.split(';').next())
+			.is_some_and(|c| c.trim().eq_ignore_ascii_case("application/json"));
+		if request.body_length().unwrap_or_default() > 0 && !json {
+			return Err((415, "Request body must be application/json"));
+		}
+
+		if request.body_length().unwrap_or_default() > MAX_BODY {
+			return Err((413, "Request body is too large"));
+		}
+
+		Ok(())
+	}
+
+	fn route(&self, method: &tiny_http::Method, url: &str, body: &str) -> (u16, Value) {
+		use tiny_http::Method::{Get,Post};
+
+		let params = || match body.trim() {
+			"" => Ok(Value::Null),
+			b  => serde_json::from_str(b).map_err(|e| Error::Invalid(format!("Invalid JSON: {e}"))),
+		};
+		let result = match (method, url) {
+			(Post, "/json_rpc") => return (200, self.json_rpc(body)),
+			(Post, "/start")    => params().and_then(|p| self.call("start", p)),
+			(Post, "/stop")     => self.call("stop", Value::Null),
+			(Get, "/status")    => self.call("status", Value::Null),
+			(Get, "/results")   => self.call("results", Value::Null),
+			(_, "/json_rpc" | "/start" | "/stop" | "/status" | "/results") => {
+				return (405, json!({ "error": { "code": 405, "message": "Method not allowed" } }));
+			},
+			_ => return (404, json!({ "error": { "code": 404, "message": "Not found" } })),
+		};
+		match result {
+			Ok(value) => (200, value),
+			Err(e)    => (e.http(), json!({ "error": { "code": e.http(), "message": e.message() } })),
+		}
+	}
+
+	fn json_rpc(&self, body: &str) -> Value {
+		#[derive(Deserialize)]
+		struct Request {
+			#[serde(default)]
+			id: Value,
+			method: String,
+			#[serde(default)]
+			params: Value,
+		}
+
+		let request: Request = match serde_json::from_str(body) {
+			Ok(r)  => r,
+			Err(e) => return json!({ "jsonrpc": "2.0", "id": null, "error": { "code": -32700, "message": format!("Parse error: {e}") } }),
+		};
+		match self.call(&request.method, request.params) {
+			Ok(result) => json!({ "jsonrpc": "2.0", "id": request.id, "result": result }),
+			Err(e) => json!({ "jsonrpc": "2.0", "id": request.id, "error": { "code": e.rpc(), "message": e.message() } }),
+		}
+	}
+}
+
+// Is `host` the address the daemon listens on?
+//
+// An unspecified address (`0.0.0.0`) takes any IP on its port, only
+// names (what DNS rebinding needs) are rejected. Loopback also takes `localhost`.
+fn host_ok(host: &str, addr: Option<SocketAddr>) -> bool {
+	let Some(addr) = addr else { return false };
+	if let Ok(host) = host.parse::<SocketAddr>() {
+		return host.port() == addr.port() && (addr.ip().is_unspecified() || host.ip() == addr.ip());
+	}
+	addr.ip().is_loopback() && host.eq_ignore_ascii_case(&format!("localhost:{}", addr.port()))
+}
+
+// Compare the token in constant time.
+fn same(a: &[u8], b: &[u8]) -> bool {
+	a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::io::Write;
+
+	const TOKEN: &str = "hunter2";
+
+	// A daemon on a random local port.
+	fn daemon() -> std::net::SocketAddr {
+		let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+		let addr = server.server_addr().to_ip().unwrap();
+		std::thread::spawn(move || Daemon::new(TOKEN.into()).serve(&server));
+		addr
+	}
+
+	fn request(addr: std::net::SocketAddr, method: &str, path: &str, body: &str) -> (u16, Value) {
+		let headers = format!("Host: {addr}\r\nAuthorization: Bearer {TOKEN}\r\nContent-Type: application/json\r\n");
+		request_with(addr, method, path, &headers, body)
+	}
+
+	fn request_with(addr: std::net::SocketAddr, method: &str, path: &str, headers: &str, body: &str) -> (u16, Value) {
+		let mut stream = std::net::TcpStream::connect(addr).unwrap();
+		write!(
+			stream,
+			"{method} {path} HTTP/1.1\r\n{headers}Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+			body.len(),
+		).unwrap();
+		let mut response = String::new();
+		stream.read_to_string(&mut response).unwrap();
+		let code = response[9..12].parse().unwrap();
+		let body = &response[response.find("\r\n\r\n").unwrap() + 4..];
+		(code, serde_json::from_str(body).unwrap())
+	}
+
+	// Wait until the search stops by itself.
+	fn wait(addr: std::net::SocketAddr) -> Value {
+		for _ in 0..600 {
+			let (_, status) = request(addr, "GET", "/status", "");
+			if status["running"] == false {
+				return status;
+			}
+			std::thread::sleep(Duration::from_millis(100));
+		}
+		panic!("search never finished");
+	}
+
+	#[test]
+	fn start_status_results() {
+		let addr = daemon();
+		assert_eq!(request(addr, "GET", "/status", "").0, 404);
+
+		let (code, status) = request(addr, "POST", "/start", r#"{"pattern":["a"],"threads":1,"count":2,"network":"stagenet"}"#);
+		assert_eq!(code, 200);
+		assert_eq!(status["patterns"], json!(["^..a.*$"]));
+
+		let status = wait(addr);
+		assert_eq!(status["found"], 2);
+		assert!(status["tries"].as_u64().unwrap() > 0);
+
+		let (code, results) = request(addr, "GET", "/results", "");
+		assert_eq!(code, 200);
+		let results = results["results"].as_array().unwrap();
+		assert_eq!(results.len(), 2);
+		for result in results {
+			let address = result["address"].as_str().unwrap();
+			assert!(address.starts_with('5') && &address[2..3] == "a");
+			assert_eq!(result["network"], "stagenet");
+			assert_eq!(result["spend"].as_str().unwrap().len(), 64);
+		}
+	}
+
+	#[test]
+	fn start_stop_conflict() {
+		let addr = daemon();
+		let start = r#"{"pattern":["hinto"],"threads":1,"count":null}"#;
+		assert_eq!(request(addr, "POST", "/start", start).0, 200);
+		assert_eq!(request(addr, "POST", "/start", start).0, 409);
+
+		let (code, status) = request(addr, "POST", "/stop", "");
+		assert_eq!(code, 200);
+		assert_eq!(status["running"], false);
+		assert_eq!(status["count"], Value::Null);
+		assert_eq!(request(addr, "POST", "/start", r#"{"pattern":["a"],"threads":1}"#).0, 200);
+	}
+
+	#[test]
+	fn invalid_requests() {
+		let addr = daemon();
+		for body in [r#"{"pattern":["hintO"]}"#, r#"{"pattern":[]}"#, r#"{"pattern":["^4z"],"first":true}"#, r#"{"pattern":"a"}"#, "{"] {
+			let (code, error) = request(addr, "POST", "/start", body);
+			assert_eq!(code, 400, "{body}");
+			assert_eq!(error["error"]["code"], 400);
+		}
+		assert_eq!(request(addr, "GET", "/start", "").0, 405);
+		assert_eq!(request(addr, "GET", "/nothing", "").0, 404);
+	}
+
+	#[test]
+	fn rejected_requests() {
+		let addr = daemon();
+		let auth = format!("Authorization: Bearer {TOKEN}\r\n");
+		let start = r#"{"pattern":["a"],"threads":1}"#;
+		let big = " ".repeat(MAX_BODY + 1);
+		for (headers, body, code) in [
+			// No/wrong token.
+			(format!("Host: {addr}\r\n"), "", 401),
+			(format!("Host: {addr}\r\nAuthorization: Bearer hunter3\r\n"), "", 401),
+			// Another host (DNS rebinding).
+			(format!("Host: evil.example:{}\r\n{auth}", addr.port()), "", 403),
+			(format!("Host: 127.0.0.2:{}\r\n{auth}", addr.port()), "", 403),
+			(auth.clone(), "", 403),
+			// Not JSON (a cross-site form).
+			(format!("Host: {addr}\r\n{auth}Content-Type: text/plain\r\n"), start, 415),
+			(format!("Host: {addr}\r\n{auth}"), start, 415),
+			// Too big.
+			(format!("Host: {addr}\r\n{auth}Content-Type: application/json\r\n"), big.as_str(), 413),
+		] {
+			let (got, error) = request_with(addr, "POST", "/start", &headers, body);
+			assert_eq!(got, code, "{headers}");
+			assert_eq!(error["error"]["code"], code);
+		}
+		assert_eq!(request(addr, "GET", "/status", "").0, 404, "nothing was started");
+
+		let headers = format!("Host: localhost:{}\r\n{auth}Content-Type: application/json; charset=utf-8\r\n", addr.port());
+		assert_eq!(request_with(addr, "POST", "/start", &headers, start).0, 200);
+	}
+
+	#[test]
+	fn hosts() {
+		let local: SocketAddr = "127.0.0.1:18090".parse().unwrap();
+		let any: SocketAddr = "0.0.0.0:18090".parse().unwrap();
+		assert!(host_ok("127.0.0.1:18090", Some(local)));
+		assert!(host_ok("LOCALHOST:18090", Some(local)));
+		assert!(!host_ok("127.0.0.1:18091", Some(local)));
+		assert!(!host_ok("localhost:18090", Some(any)));
+		assert!(host_ok("192.168.1.2:18090", Some(any)));
+		assert!(!host_ok("evil.example:18090", Some(any)));
+		assert!(!host_ok("127.0.0.1:18090", None));
+	}
+
+	#[test]
+	fn json_rpc() {
+		let addr = daemon();
+		let (_, response) = request(addr, "POST", "/json_rpc", r#"{"jsonrpc":"2.0","id":1,"method":"start","params":{"pattern":["a"],"threads":1}}"#);
+		assert_eq!(response["id"], 1);
+		assert_eq!(response["result"]["running"], true);
+
+		wait(addr);
+		let (_, response) = request(addr, "POST", "/json_rpc", r#"{"jsonrpc":"2.0","id":"x","method":"results"}"#);
+		assert_eq!(response["id"], "x");
+		assert_eq!(response["result"]["results"].as_array().unwrap().len(), 1);
+
+		let (_, response) = request(addr, "POST", "/json_rpc", r#"{"jsonrpc":"2.0","id":2,"method":"nope"}"#);
+		assert_eq!(response["error"]["code"], -32601);
+		let (_, response) = request(addr, "POST", "/json_rpc", "not json");
+		assert_eq!(response["error"]["code"], -32700);
+	}
+}
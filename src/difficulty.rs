@@ -0,0 +1,490 @@
+//---------------------------------------------------------------------------------------------------- Use
+use crate::encode::{BASE58_CHARS,CHUNK,SPEND_CHARS};
+use crate::network::Network;
+use rand::Rng;
+use readable::Time;
+use regex::Regex;
+use regex_syntax::hir::{Class,Hir,HirKind,Look};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+//---------------------------------------------------------------------------------------------------- Constants
+/// How many random addresses are tried when a regex can't be worked out exactly.
+const SAMPLES: usize = 100_000;
+
+/// How many characters the tail (integrated address/suffix) is.
+const TAIL_CHARS: usize = 18;
+
+/// Every base58 character.
+const ANY: u64 = (1 << 58) - 1;
+
+lazy_static::lazy_static! {
+	// Every regex already estimated, sampling takes a moment
+	// and the GUI estimates the same pattern on every `Start`.
+	static ref ESTIMATED: Mutex<HashMap<(String, Target), Option<Chance>>> = Mutex::new(HashMap::new());
+}
+
+//---------------------------------------------------------------------------------------------------- Target
+/// What part of the address a pattern is matched against.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Target {
+	/// The start of an address with this network byte.
+	Prefix(u8),
+	/// The last `18` characters of an address.
+	Tail,
+}
+
+//---------------------------------------------------------------------------------------------------- Chance
+/// The chance a single try matches a pattern.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Chance {
+	/// The probability per try (`0.0..=1.0`).
+	pub p: f64,
+	/// Was this estimated by sampling random addresses instead of worked out exactly?
+	pub sampled: bool,
+}
+
+impl Chance {
+	/// How many tries it takes on average.
+	pub fn tries(&self) -> f64 {
+		1.0 / self.p
+	}
+
+	/// How many tries until there is a `probability` chance of a match.
+	pub fn tries_for(&self, probability: f64) -> f64 {
+		(1.0 - probability).ln() / (-self.p).ln_1p()
+	}
+
+	/// The chance at least 1 match was found after `tries`.
+	pub fn found_by(&self, tries: u64) -> f64 {
+		1.0 - (tries as f64 * (-self.p).ln_1p()).exp()
+	}
+
+	/// How long until there is a `probability` chance of a match at `speed` tries per second.
+	pub fn eta(&self, probability: f64, speed: u64) -> Option<Time> {
+		eta(self.tries_for(probability), speed)
+	}
+
+	/// `50%/90%/99%` times at `speed`, e.g: `8s/26s/53s`.
+	pub fn etas(&self, speed: u64) -> Option<String> {
+		let [a, b, c] = [0.5, 0.9, 0.99].map(|p| self.eta(p, speed));
+		Some(format!("{}/{}/{}", a?, b?, c?))
+	}
+}
+
+impl std::fmt::Display for Chance {
+	/// `1 in 656,356,768` or `1 in ~1,234 (sampled)`.
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if self.p <= 0.0 {
+			return write!(f, "Never");
+		}
+		let tries = readable::Unsigned::from(self.tries().round() as u64);
+		match self.sampled {
+			false => write!(f, "1 in {tries}"),
+			true  => write!(f, "1 in ~{tries} (sampled)"),
+		}
+	}
+}
+
+//---------------------------------------------------------------------------------------------------- Estimate
+/// The chance a single try matches `regex` at `target`.
+///
+/// Plain characters and classes anchored to the start/end are worked out
+/// exactly from the base58 encoding, including the biased first character
+/// of each block and the network byte. Anything else is estimated by
+/// matching random addresses.
+///
+/// `None` if the regex doesn't parse, or is too rare to estimate by sampling.
+///
+/// Sampling takes a moment, so every result is kept and
+/// UIs should call this off their thread (see [`spawn()`]).
+pub fn chance(regex: &str, target: Target) -> Option<Chance> {
+	let key = (regex.to_string(), target);
+	if let Some(chance) = ESTIMATED.lock().unwrap().get(&key) {
+		return *chance;
+	}
+
+	let chance = estimate(regex, target);
+	ESTIMATED.lock().unwrap().insert(key, chance);
+	chance
+}
+
+// [`chance()`] without the cache.
+fn estimate(regex: &str, target: Target) -> Option<Chance> {
+	let hir = regex_syntax::Parser::new().parse(regex).ok()?;
+	if let Some(p) = exact(&hir, target) {
+		return Some(Chance { p, sampled: false });
+	}
+
+	let regex = Regex::new(regex).ok()?;
+	let hits = (0..SAMPLES).filter(|_| regex.is_match(&sample(target, &regex))).count();
+	match hits {
+		0 => None,
+		_ => Some(Chance { p: hits as f64 / SAMPLES as f64, sampled: true }),
+	}
+}
+
+/// Estimate on a new thread, [`std::thread::JoinHandle::is_finished()`] tells when it is done.
+pub fn spawn<T: Send + 'static>(estimate: impl FnOnce() -> T + Send + 'static) -> std::thread::JoinHandle<T> {
+	std::thread::spawn(estimate)
+}
+
+/// The chance a single try matches _any_ of `regexes`.
+///
+/// The chances add up (it is rare for 2 to match at once).
+pub fn chance_any<S: AsRef<str>>(regexes: &[S], target: Target) -> Option<Chance> {
+	let mut any = Chance { p: 0.0, sampled: false };
+	for regex in regexes {
+		let chance = chance(regex.as_ref(), target)?;
+		any.p       += chance.p;
+		any.sampled |= chance.sampled;
+	}
+	match regexes.is_empty() {
+		true  => None,
+		false => Some(Chance { p: any.p.min(1.0), ..any }),
+	}
+}
+
+/// How long `tries` takes at `speed` tries per second.
+pub fn eta(tries: f64, speed: u64) -> Option<Time> {
+	match speed {
+		0 => None,
+		_ => Some(Time::from(tries / speed as f64)),
+	}
+}
+
+//---------------------------------------------------------------------------------------------------- Reachability
+/// The base58 characters that can be at character `i` of `target`.
+///
+/// Mainnet addresses always start with `4` and then one of `123456789AB`.
+pub fn reachable(target: Target, i: usize) -> String {
+	let mask = reachable_mask(target, i);
+	BASE58_CHARS.iter().enumerate().filter(|(d, _)| mask & 1 << d != 0).map(|(_, c)| *c as char).collect()
+}
+
+/// Which part of which address `target` is, for messages (e.g. `of a Mainnet address`).
+pub fn place(target: Target) -> String {
+	let Target::Prefix(byte) = target else {
+		return "of the last 18 characters".into();
+	};
+	for network in [Network::Mainnet, Network::Stagenet, Network::Testnet] {
+		if byte == network.byte() {
+			return format!("of a {network} address");
+		} else if byte == network.subaddress_byte() {
+			return format!("of a {network} subaddress");
+		}
+	}
+	"of the address".into()
+}
+
+/// The first character of the address `regex` needs that can never be what it needs.
+///
+/// Only the fixed characters anchored to the start (or to the end of the tail)
+/// are checked, so `^4z` is unreachable at `1` on Mainnet while
+/// `^(4z|5a)` can't be checked (only [`chance()`] can tell).
+pub fn unreachable(regex: &str, target: Target) -> Option<usize> {
+	let hir = regex_syntax::Parser::new().parse(regex).ok()?;
+	let subs = match hir.kind() {
+		HirKind::Concat(subs) => subs.as_slice(),
+		_ => std::slice::from_ref(&hir),
+	};
+	let len = match target {
+		Target::Prefix(_) => SPEND_CHARS,
+		Target::Tail      => TAIL_CHARS,
+	};
+
+	let (masks, offset) = match (subs.first().map(Hir::kind), subs.last().map(Hir::kind), target) {
+		(Some(HirKind::Look(Look::Start)), _, _) => (fixed(subs[1..].iter()).concat(), 0),
+		(_, Some(HirKind::Look(Look::End)), Target::Tail) => {
+			let mut groups = fixed(subs[..subs.len() - 1].iter().rev());
+			groups.reverse();
+			let masks = groups.concat();
+			let Some(offset) = len.checked_sub(masks.len()) else {
+				return Some(0);
+			};
+			(masks, offset)
+		},
+		_ => return None,
+	};
+
+	masks.iter().enumerate().find_map(|(i, mask)| {
+		let i = offset + i;
+		(i >= len || mask & reachable_mask(target, i) == 0).then_some(i)
+	})
+}
+
+// The fixed characters of `subs` until the first one that isn't (grouped by `Hir`).
+fn fixed<'a>(subs: impl Iterator<Item = &'a Hir>) -> Vec<Vec<u64>> {
+	let mut groups = vec![];
+	for sub in subs {
+		let mut masks = vec![];
+		if masks_of(sub, &mut masks).is_none() {
+			break;
+		}
+		groups.push(masks);
+	}
+	groups
+}
+
+// A bit for every base58 character that can be at `i`.
+fn reachable_mask(target: Target, i: usize) -> u64 {
+	let (counts, _) = digits(target, i);
+	counts.iter().enumerate().fold(0, |mask, (d, n)| match n {
+		0 => mask,
+		_ => mask | 1 << d,
+	})
+}
+
+// The exact chance, if `hir` is a row of characters/classes anchored at the start/end.
+fn exact(hir: &Hir, target: Target) -> Option<f64> {
+	let mut subs = match hir.kind() {
+		HirKind::Concat(subs) => subs.as_slice(),
+		_ => std::slice::from_ref(hir),
+	};
+
+	let start = matches!(subs.first().map(Hir::kind), Some(HirKind::Look(Look::Start)));
+	if start {
+		subs = &subs[1..];
+	}
+	// A trailing `$` only anchors if it isn't after a `.*`,
+	// which never decides a match.
+	let mut end = false;
+	if let Some((HirKind::Look(Look::End), rest)) = subs.split_last().map(|(last, rest)| (last.kind(), rest)) {
+		subs = rest;
+		end  = !rest.last().is_some_and(is_any);
+	}
+	while let Some((last, rest)) = subs.split_last() {
+		if !is_any(last) {
+			break;
+		}
+		subs = rest;
+	}
+
+	let mut masks = Vec::new();
+	for sub in subs {
+		masks_of(sub, &mut masks)?;
+	}
+	let len = match target {
+		Target::Prefix(_) => SPEND_CHARS,
+		Target::Tail      => TAIL_CHARS,
+	};
+	if masks.len() > len {
+		return Some(0.0);
+	}
+
+	let at = |offset: usize| masks.iter().enumerate().map(|(i, m)| digit_chance(target, offset + i, *m)).product::<f64>();
+	match (target, start, end) {
+		(Target::Prefix(_), true, false) => Some(at(0)),
+		(Target::Tail, true, false)      => Some(at(0)),
+		(Target::Tail, false, true)      => Some(at(len - masks.len())),
+		// An infix can be at any offset.
+		(Target::Tail, false, false)     => Some((0..=len - masks.len()).map(at).sum::<f64>().min(1.0)),
+		_ => None,
+	}
+}
+
+// Is this a `.*`?
+fn is_any(hir: &Hir) -> bool {
+	match hir.kind() {
+		HirKind::Repetition(r) => r.min == 0 && r.max.is_none() && mask(&r.sub) == Some(ANY),
+		_ => false,
+	}
+}
+
+// Push the base58 characters each position of `hir` can be.
+fn masks_of(hir: &Hir, masks: &mut Vec<u64>) -> Option<()> {
+	match hir.kind() {
+		HirKind::Empty => (),
+		HirKind::Literal(l) => {
+			for c in String::from_utf8_lossy(&l.0).chars() {
+				masks.push(mask_of(|b| b as char == c));
+			}
+		},
+		HirKind::Class(_)      => masks.push(mask(hir)?),
+		HirKind::Capture(c)    => masks_of(&c.sub, masks)?,
+		HirKind::Concat(subs)  => for sub in subs { masks_of(sub, masks)? },
+		HirKind::Repetition(r) if r.max == Some(r.min) => for _ in 0..r.min { masks_of(&r.sub, masks)? },
+		_ => return None,
+	}
+	Some(())
+}
+
+// The base58 characters a single character class can be.
+fn mask(hir: &Hir) -> Option<u64> {
+	match hir.kind() {
+		HirKind::Class(Class::Unicode(c)) => Some(mask_of(|b| c.ranges().iter().any(|r| r.start() <= b as char && b as char <= r.end()))),
+		HirKind::Class(Class::Bytes(c))   => Some(mask_of(|b| c.ranges().iter().any(|r| r.start() <= b && b <= r.end()))),
+		_ => None,
+	}
+}
+
+// A bit for every base58 character that is `in`.
+fn mask_of(is_in: impl Fn(u8) -> bool) -> u64 {
+	BASE58_CHARS.iter().enumerate().fold(0, |mask, (i, b)| match is_in(*b) {
+		true  => mask | 1 << i,
+		false => mask,
+	})
+}
+
+// How many block values give each base58 character at `i` (+ the total).
+//
+// The first character of each block is biased,
+// and the network byte fixes most of the first `2` characters.
+fn digits(target: Target, i: usize) -> ([u128; 58], u128) {
+	let (digit, digits, low, high): (usize, usize, u128, u128) = match target {
+		Target::Prefix(byte) if i < CHUNK => (i, CHUNK, (byte as u128) << 56, (byte as u128 + 1) << 56),
+		Target::Prefix(_)                 => (i % CHUNK, CHUNK, 0, 1 << 64),
+		Target::Tail if i < CHUNK         => (i, CHUNK, 0, 1 << 64),
+		Target::Tail                      => (i - CHUNK, 7, 0, 1 << 40),
+	};
+	(crate::encode::digit_counts(digit, digits, low, high), high - low)
+}
+
+// The chance the character at `i` is one of `mask`.
+fn digit_chance(target: Target, i: usize, mask: u64) -> f64 {
+	let (counts, total) = digits(target, i);
+	let hits: u128 = (0..58)
+		.filter(|d| mask & 1 << d != 0)
+		.map(|d| counts[d])
+		.sum();
+	hits as f64 / total as f64
+}
+
+// A random string of what `regex` is matched against.
+fn sample(target: Target, regex: &Regex) -> String {
+	let mut rng = rand::thread_rng();
+	match target {
+		Target::Prefix(byte) => {
+			let mut data = [0; 33];
+			data[0] = byte;
+			rng.fill(&mut data[1..]);
+			let mut out = [0; SPEND_CHARS];
+			let len = crate::encode::encode_blocks(&data, crate::regexes::blocks(regex.as_str()), &mut out);
+			String::from_utf8_lossy(&out[..len]).into_owned()
+		},
+		Target::Tail => {
+			let mut data = [0; 13];
+			rng.fill(&mut data[..]);
+			let mut out = crate::encode::encode_11(&data).to_vec();
+			out.extend(crate::encode::encode_7(&data[8..]));
+			String::from_utf8_lossy(&out).into_owned()
+		},
+	}
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const MAINNET: Target = Target::Prefix(18);
+
+	fn p(regex: &str, target: Target) -> f64 {
+		chance(regex, target).unwrap().p
+	}
+
+	// Within 5%, the first block is slightly biased by the network byte.
+	fn close(a: f64, b: f64) -> bool {
+		(a / b - 1.0).abs() < 0.05
+	}
+
+	#[test]
+	fn plain_patterns() {
+		// Past the 2nd character, every character is (almost) `1` in `58`.
+		assert!(close(p("^..hinto.*$", MAINNET), 58_f64.powi(-5)));
+		assert!(close(p("^..[hH]i[nN][tT]o.*$", MAINNET), 8.0 * 58_f64.powi(-5)));
+		assert!(!chance("^..hinto.*$", MAINNET).unwrap().sampled);
+		assert_eq!(p("^..hintO.*$", MAINNET), 0.0);
+	}
+
+	#[test]
+	fn network_byte() {
+		// Mainnet always starts with `4`, then `1-9AB`.
+		assert_eq!(p("^4", MAINNET), 1.0);
+		assert_eq!(p("^5", MAINNET), 0.0);
+		assert_eq!(p("^4z", MAINNET), 0.0);
+		assert!(close(p("^.[1-9AB]", MAINNET), 1.0));
+		// Subaddresses start with `8`.
+		assert_eq!(p("^8", Target::Prefix(42)), 1.0);
+	}
+
+	#[test]
+	fn block_bias() {
+		// The 1st character of each block can't be past the ~43rd base58 character.
+		assert_eq!(p("^...........z", MAINNET), 0.0);
+		assert!(p("^...........2", MAINNET) > 1.0 / 58.0);
+	}
+
+	#[test]
+	fn tail_patterns() {
+		assert!(close(p("hinto$", Target::Tail), 58_f64.powi(-5)));
+		// An infix can be at any of the `14` offsets (some are biased).
+		let infix = p("hinto", Target::Tail) / 58_f64.powi(-5);
+		assert!(infix > 8.0 && infix < 14.0);
+		// The last block is `5` bytes, so its 1st character is small.
+		assert_eq!(p("z......$", Target::Tail), 0.0);
+	}
+
+	#[test]
+	fn sampled() {
+		let sampled = chance("^..(a|bc).*$", MAINNET).unwrap();
+		assert!(sampled.sampled);
+		assert!((sampled.p - (1.0 / 58.0 + 1.0 / 58.0 / 58.0)).abs() < 0.005);
+		assert_eq!(chance("^..hintojanai.*$|^..(x|y)z{9}", MAINNET), None);
+	}
+
+	#[test]
+	fn many_patterns() {
+		let any = chance_any(&["^..ab.*$", "^..cd.*$"], MAINNET).unwrap();
+		assert_eq!(any.p, p("^..ab.*$", MAINNET) + p("^..cd.*$", MAINNET));
+		assert_eq!(chance_any::<&str>(&[], MAINNET), None);
+	}
+
+	#[test]
+	fn reachable_characters() {
+		assert_eq!(reachable(MAINNET, 0), "4");
+		assert_eq!(reachable(MAINNET, 1), "123456789AB");
+		assert_eq!(reachable(Target::Prefix(42), 0), "8");
+		assert_eq!(reachable(MAINNET, 2).len(), 58);
+		// The 1st character of a block.
+		assert!(!reachable(MAINNET, 11).contains('z'));
+		assert!(!reachable(Target::Tail, 11).contains('z'));
+	}
+
+	#[test]
+	fn unreachable_patterns() {
+		assert_eq!(unreachable("^4z", MAINNET), Some(1));
+		assert_eq!(unreachable("^..z", MAINNET), None);
+		assert_eq!(unreachable("^5", MAINNET), Some(0));
+		assert_eq!(unreachable("^.[AB]hinto.*$", MAINNET), None);
+		assert_eq!(unreachable("^.[CD]hinto.*$", MAINNET), Some(1));
+		assert_eq!(unreachable("^..(a|bc)........z", MAINNET), None);
+		assert_eq!(unreachable("^..a........z", MAINNET), Some(11));
+		assert_eq!(unreachable("z......$", Target::Tail), Some(11));
+		assert_eq!(unreachable("hinto$", Target::Tail), None);
+		assert_eq!(unreachable("^..hinto.*$", Target::Tail), None);
+		// Can't tell.
+		assert_eq!(unreachable("^(4z|5a)", MAINNET), None);
+		assert_eq!(unreachable("hinto", MAINNET), None);
+	}
+
+	#[test]
+	// Sampled estimates are kept, so the same regex gives the same (instant) answer.
+	fn cached() {
+		let regex = "^..(q|rs).*$";
+		let first = chance(regex, MAINNET).unwrap();
+		assert!(first.sampled);
+		assert_eq!(chance(regex, MAINNET), Some(first));
+		assert_eq!(spawn(move || chance(regex, MAINNET)).join().unwrap(), Some(first));
+	}
+
+	#[test]
+	fn time() {
+		let chance = Chance { p: 0.5, sampled: false };
+		assert_eq!(chance.tries(), 2.0);
+		assert!((chance.tries_for(0.75) - 2.0).abs() < 0.001);
+		assert!((chance.found_by(2) - 0.75).abs() < 0.001);
+		assert_eq!(chance.to_string(), "1 in 2");
+		assert_eq!(Chance { p: 0.0, sampled: false }.to_string(), "Never");
+	}
+}
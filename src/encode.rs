@@ -18,13 +18,17 @@
 
 //---------------------------------------------------------------------------------------------------- Constants
 // Base58 alphabet, does not contains visualy similar characters
-const BASE58_CHARS: [u8; 58] = *b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+pub const BASE58_CHARS: [u8; 58] = *b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
 const BASE58_CHARS_LEN: u64 = BASE58_CHARS.len() as u64;
 
 // The character chunks we're operating on.
 // This is the max for Monero's `base58` and
 // equate to around `8` bytes.
-const CHUNK: usize = 11;
+pub const CHUNK: usize = 11;
+
+/// How many characters of an address only depend on the
+/// network byte + public spend key (`4` blocks of `8` bytes).
+pub const SPEND_CHARS: usize = 44;
 
 // Unrolled `u8be_to_u64()` function loop as a macro.
 macro_rules! n {
@@ -72,11 +76,99 @@ pub fn encode_11(data: &[u8]) -> [u8; CHUNK] {
 	res
 }
 
+#[inline(always)]
+/// Encode the first `blocks` (`1..=4`) blocks of
+/// `network byte || public spend key` into `out`.
+///
+/// Returns how many characters were written.
+/// `1` block is the same as [`encode_11()`].
+pub fn encode_blocks(data: &[u8; 33], blocks: usize, out: &mut [u8; SPEND_CHARS]) -> usize {
+	for i in 0..blocks {
+		out[i * CHUNK..(i + 1) * CHUNK].copy_from_slice(&encode_11(&data[i * 8..]));
+	}
+	blocks * CHUNK
+}
+
+#[inline(always)]
+/// Same as [`encode_11()`] but for the last `5` byte
+/// block of an integrated address, which is `7` characters.
+///
+/// INVARIANT:
+/// Only uses the first `5` bytes of input.
+pub fn encode_7(data: &[u8]) -> [u8; 7] {
+	let mut res = [0; 7];
+	let mut num = {
+		let mut num: u64 = 0;
+		n!(data, 0, num);
+		n!(data, 1, num);
+		n!(data, 2, num);
+		n!(data, 3, num);
+		n!(data, 4, num);
+		num
+	};
+	r!(6, num, res);
+	r!(5, num, res);
+	r!(4, num, res);
+	r!(3, num, res);
+	r!(2, num, res);
+	r!(1, num, res);
+	r!(0, num, res);
+	res
+}
+
+/// How many block values in `low..high` give each base58
+/// character at `digit` of a `digits` long block.
+///
+/// `8` bytes become `11` characters (`5` become `7`) and `58^11 > 2^64`,
+/// so the first character of a block can only be one of the first ~`43`.
+/// The network byte fixes `low..high` of the first block even more.
+pub fn digit_counts(digit: usize, digits: usize, low: u128, high: u128) -> [u128; 58] {
+	// How many numbers below `n` have `d` at this digit.
+	let place = 58_u128.pow((digits - digit - 1) as u32);
+	let count = |n: u128, d: u128| (n / (58 * place)) * place + ((n % (58 * place)).saturating_sub(d * place)).min(place);
+
+	std::array::from_fn(|d| count(high, d as u128) - count(low, d as u128))
+}
+
 //---------------------------------------------------------------------------------------------------- TESTS
 #[cfg(test)]
 mod tests {
 	use super::*;
 
+	#[test]
+	// Compare against `base58-monero` for random blocks.
+	fn encode_matches_base58_monero() {
+		use rand::Rng;
+		let mut rng = rand::thread_rng();
+		for _ in 0..10_000 {
+			let block: [u8; 8] = rng.gen();
+			let expected = base58_monero::encode(&block).unwrap();
+			assert_eq!(std::str::from_utf8(&encode_11(&block)).unwrap(), expected);
+
+			let block: [u8; 5] = rng.gen();
+			let expected = base58_monero::encode(&block).unwrap();
+			assert_eq!(std::str::from_utf8(&encode_7(&block)).unwrap(), expected);
+		}
+	}
+
+	#[test]
+	// The blocks must be the start of the real base58 address.
+	fn encode_blocks_matches_base58_monero() {
+		use rand::Rng;
+		let mut rng = rand::thread_rng();
+		for _ in 0..1_000 {
+			let mut data = [0_u8; 33];
+			rng.fill(&mut data[..]);
+			let expected = base58_monero::encode(&data).unwrap();
+
+			let mut out = [0_u8; SPEND_CHARS];
+			for blocks in 1..=4 {
+				let len = encode_blocks(&data, blocks, &mut out);
+				assert_eq!(std::str::from_utf8(&out[..len]).unwrap(), &expected[..len]);
+			}
+		}
+	}
+
 	#[test]
 	#[ignore]
 	// This test checks _every_ 11-byte array
@@ -13,15 +13,31 @@ use crate::constants::{
 	APP_RESOLUTION,
 	DARK_GRAY,
 	THIRD,FIRST,
+	STANDARD,INTEGRATED,
+	SUFFIX,INFIX,
+	NETWORK,COUNT,IGNORE_CASE,LEET,RESULTS,
+	QUEUE,SCHEDULE,SLICE,
+	SEARCH,SPLIT_KEY,SPLIT_GENERATE,SPLIT_SEARCH,SPLIT_JOB,SPLIT_JOIN,
 	STATS,HISTORY,
 };
 use crate::threads::{
 	THREADS_MAX,
 	THREADS_HALF,
 };
-use crate::pattern::PatternType;
-use regex::Regex;
-use std::time::Instant;
+use crate::pattern::{PatternType,TailType};
+use crate::mode::Mode;
+use crate::hit::Hit;
+use crate::tab::Tab;
+use crate::network::Network;
+use crate::mnemonic::Language;
+use crate::regexes::Patterns;
+use crate::leet::Leet;
+use crate::difficulty::{Chance,Target};
+use crate::queue::{Queue,Job,Schedule,Update};
+use crate::results::Results;
+use curve25519_dalek::edwards::EdwardsPoint;
+use std::time::{Duration,Instant};
+use std::str::FromStr;
 use std::fmt::Write;
 use readable::{
 	Unsigned,
@@ -32,48 +48,172 @@ use readable::{
 #[derive(Debug)]
 pub struct Gui {
 	/// Channel to `GUI`.
-	to: std::sync::mpsc::Sender::<(String, String, String)>,
+	to: std::sync::mpsc::Sender::<Hit>,
 
 	/// Channel from `worker` threads.
-	from: std::sync::mpsc::Receiver::<(String, String, String)>,
+	from: std::sync::mpsc::Receiver::<Hit>,
 
 	/// General State.
 	state: State,
 
+	/// The chance (+ how many times more likely) of the search, until it is estimated.
+	estimate: Option<std::thread::JoinHandle<(Option<Chance>, Option<f64>)>>,
+
 	/// Third vs First
 	pattern_type: PatternType,
 
+	/// Mainnet vs Stagenet vs Testnet
+	network: Network,
+
+	/// Standard vs Integrated
+	mode: Mode,
+
+	/// Suffix vs Infix
+	tail_type: TailType,
+
+	/// Current user-input standard address (integrated mode).
+	address: String,
+
+	/// Old user-input standard address.
+	old_address: String,
+
+	/// The parsed standard address (integrated mode).
+	address_parsed: Result<monero::Address, &'static str>,
+
 	/// Current user-input pattern.
 	pattern: String,
 
-	/// Old user-input pattern.
+	/// Old full regex (of the user-input pattern).
 	old_pattern: String,
 
+	/// The network the old regex was checked against.
+	old_network: Network,
+
 	/// Current user-input threads.
 	threads: usize,
 
+	/// Current user-input amount of addresses to find.
+	count: u64,
+
+	/// Keep finding addresses until stopped?
+	infinite: bool,
+
+	/// Match every base58 case of the pattern?
+	ignore_case: bool,
+
+	/// Match leetspeak/lookalikes of the pattern?
+	leet: bool,
+
+	/// Current user-input results file path.
+	results_path: String,
+
+	/// Current user-input results file password.
+	results_password: String,
+
+	/// The results file found keys are written to.
+	results: Option<Results>,
+
+	/// Show found keys in the history instead of a results file?
+	show_keys: bool,
+
 	/// Is the address pattern valid?
 	regex_ok: bool,
 
 	/// Why did the regex fail?
-	regex_fail: &'static str,
+	regex_fail: String,
+
+	/// Jobs waiting to be run as a queue.
+	jobs: Vec<Job>,
+
+	/// The running queue.
+	queue: Option<Queue>,
+
+	/// Sequential vs Round-robin
+	schedule: Schedule,
+
+	/// Seconds per round-robin time slice.
+	slice: u64,
+
+	/// Search vs Split Key
+	tab: Tab,
+
+	/// The generated private + public split key part.
+	split_generated: Option<(String, String)>,
+
+	/// Current user-input public split key parts (split key search).
+	split_publics: String,
+
+	/// Old user-input public split key parts.
+	old_split_publics: String,
+
+	/// The parsed public split key parts, or why they are invalid.
+	split_publics_parsed: Result<Vec<EdwardsPoint>, String>,
+
+	/// The last calculated part found (first 44 address characters + `vanity...` blob).
+	split_found: Option<(String, String)>,
+
+	/// User-input job file path, the agreed job joining is checked against.
+	split_job_path: String,
+
+	/// Where the job file was written, or why it couldn't be.
+	split_job_written: Option<Result<String, String>>,
+
+	/// Current user-input calculated part (`vanity...`).
+	split_calculated: String,
+
+	/// Current user-input private split key parts.
+	split_privates: String,
+
+	/// The joined address, private spend/view keys + seed, or why joining failed.
+	split_joined: Option<Result<[String; 4], String>>,
 }
 
 impl Default for Gui {
 	fn default() -> Self {
-		let (to, from) = std::sync::mpsc::channel::<(String, String, String)>();
+		let (to, from) = std::sync::mpsc::channel::<Hit>();
 
 		Self {
 			to,
 			from,
 
 			state: State::default(),
+			estimate: None,
 			pattern_type: PatternType::default(),
+			network: Network::default(),
+			mode: Mode::default(),
+			tail_type: TailType::default(),
+			address: String::new(),
+			old_address: String::new(),
+			address_parsed: Err("Address must not be empty"),
 			pattern: String::new(),
 			old_pattern: String::new(),
+			old_network: Network::default(),
 			threads: *THREADS_HALF,
+			count: 1,
+			infinite: false,
+			ignore_case: false,
+			leet: false,
+			results_path: String::new(),
+			results_password: String::new(),
+			results: None,
+			show_keys: false,
 			regex_ok: false,
-			regex_fail: "Address pattern must not be empty",
+			regex_fail: "Address pattern must not be empty".into(),
+			jobs: Vec::new(),
+			queue: None,
+			schedule: Schedule::default(),
+			slice: 60,
+			tab: Tab::default(),
+			split_generated: None,
+			split_publics: String::new(),
+			old_split_publics: String::new(),
+			split_publics_parsed: Err("Enter the public split key part of every contributor".into()),
+			split_found: None,
+			split_job_path: String::new(),
+			split_job_written: None,
+			split_calculated: String::new(),
+			split_privates: String::new(),
+			split_joined: None,
 		}
 	}
 }
@@ -121,6 +261,113 @@ impl Gui {
 		options
 	}
 
+	// The current user-input pattern, expanded if ignoring case/leetspeak.
+	fn pattern(&self) -> String {
+		match (self.ignore_case, self.leet) {
+			(false, false) => self.pattern.to_string(),
+			_ => crate::regexes::expand(&self.pattern, |c| self.alternatives(c)),
+		}
+	}
+
+	// The characters `c` of the user-input pattern can be.
+	fn alternatives(&self, c: char) -> Vec<char> {
+		crate::leet::alternatives(self.leet.then(Leet::default).as_ref(), c, self.ignore_case)
+	}
+
+	// The full regex of the current user-input pattern + settings.
+	fn regex(&self) -> String {
+		self.wrap(&self.pattern())
+	}
+
+	// The full regex of `pattern` with the current settings.
+	fn wrap(&self, pattern: &str) -> String {
+		match (self.pattern_type, self.mode) {
+			(PatternType::First, _)                => pattern.to_string(),
+			(PatternType::Third, Mode::Integrated) => self.tail_type.regex(pattern),
+			(PatternType::Third, Mode::Standard)   => format!("^..{pattern}.*$"),
+		}
+	}
+
+	// A queue job of the current user-input settings.
+	fn job(&self) -> Result<Job, crate::queue::Error> {
+		let address = (self.mode == Mode::Integrated).then_some(self.address.as_str());
+		Job::new(&self.pattern, &self.pattern(), self.regex(), self.mode, address, self.count, self.network)
+	}
+
+	// What part of the address the pattern is matched against.
+	fn target(&self) -> Target {
+		match self.mode {
+			Mode::Standard   => Target::Prefix(self.network.byte()),
+			Mode::Integrated => Target::Tail,
+		}
+	}
+
+	// A new channel + signals for a new search, workers of the
+	// last one may still be finishing their batch and sending.
+	fn reset_workers(&mut self) {
+		(self.to, self.from) = std::sync::mpsc::channel::<Hit>();
+		self.state.iter = std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0));
+		self.state.die  = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+	}
+
+	// Can a search start? Found keys need somewhere to go.
+	fn keys_ok(&self) -> bool {
+		self.show_keys || !(self.results_path.is_empty() || self.results_password.is_empty())
+	}
+
+	// Open the user-input results file for a new search,
+	// writes why it couldn't to the history.
+	fn open_results(&mut self) -> bool {
+		self.results = None;
+		if self.results_path.is_empty() {
+			return self.show_keys;
+		}
+		match Results::open(std::path::Path::new(&self.results_path), &self.results_password) {
+			Ok(results) => { self.results = Some(results); true },
+			Err(e) => { writeln!(self.state.history, "Results file [{}]: {e}\n", self.results_path); false },
+		}
+	}
+
+	// Write a found wallet to the history, its private keys go to the
+	// results file if there is one (or the history with `Show keys`).
+	// Keys that could not be saved are never dropped, they are shown with a warning.
+	fn keep(history: &mut String, results: Option<&mut Results>, public: &str, private: &str, stats: &str) {
+		let saved = results.map(|results| results.add(&format!("{public}\n{private}")).map(|_| results.path().display().to_string()));
+		match saved {
+			Some(Ok(path)) => writeln!(history, "{public}\nSaved to  | {path}\n{stats}\n"),
+			Some(Err(e))   => writeln!(history, "{public}\n!!! NOT SAVED ({e}), COPY THESE KEYS NOW !!!\n{private}\n{stats}\n"),
+			None           => writeln!(history, "{public}\n{private}\n{stats}\n"),
+		};
+	}
+
+	// Write the job of the user-input public parts with the Search tab settings.
+	fn write_job(&self) -> Result<String, String> {
+		if self.mode != Mode::Standard || !self.regex_ok {
+			return Err("Enter the pattern to ask for in the Search tab (`Standard`)".into());
+		}
+		let publics = self.split_publics.split_whitespace().map(String::from).collect();
+		let job = crate::split::JobFile::new(publics, self.pattern(), self.pattern_type, self.count, self.network, String::new(), self.state.random_view);
+		job.write(std::path::Path::new(&self.split_job_path)).map_err(|e| e.to_string())?;
+		Ok(self.split_job_path.clone())
+	}
+
+	// Join the user-input calculated part with the private parts,
+	// checked against the agreed job file (never the helper's claims).
+	fn join(&self) -> Result<[String; 4], String> {
+		let job = crate::split::JobFile::read(std::path::Path::new(&self.split_job_path))
+			.map_err(|e| format!("Job file [{}]: {e}", self.split_job_path))?;
+		let proof = crate::split::Proof::from_blob(&self.split_calculated).map_err(|e| e.to_string())?;
+		let privates = self.split_privates.split_whitespace().map(|key|
+			monero::PrivateKey::from_str(key).map_err(|_| format!("Private split key part [{key}] is not a valid scalar"))
+		).collect::<Result<Vec<_>, _>>()?;
+		let (address, spend, view) = proof.join(&job, &privates, job.random_view).map_err(|e| e.to_string())?;
+		let seed = match job.random_view {
+			true  => String::new(),
+			false => crate::mnemonic::encode_str(&spend, Language::English).unwrap_or_default(),
+		};
+		Ok([address, spend, view, seed])
+	}
+
 	#[inline(always)]
 	pub fn init(cc: &eframe::CreationContext<'_>) -> Self {
 		let gui = Self::default();
@@ -133,6 +380,169 @@ impl Gui {
 	}
 }
 
+//---------------------------------------------------------------------------------------------------- Split key tab.
+impl Gui {
+	// Generate a part, search for public parts, join the parts.
+	fn split_key_tab(&mut self, ui: &mut egui::Ui, width: f32, text: f32) {
+		let width = width - 15.0;
+
+		//-------------------------------------------------- Generate.
+		ui.group(|ui| {
+			ui.set_enabled(!self.state.iterating);
+			if ui.add_sized([width, text], Button::new("Generate split key part")).on_hover_text(SPLIT_GENERATE).clicked() {
+				self.split_generated = Some(crate::address::calculate_part_split_key());
+			}
+			if let Some((private, public)) = &self.split_generated {
+				copy_row(ui, width, text, "Private Split Key (keep secret, only needed to join)", private, RED);
+				copy_row(ui, width, text, "Public Split Key (give this out)", public, GREEN);
+			}
+		});
+
+		//-------------------------------------------------- Search.
+		// Public parts check.
+		if self.split_publics != self.old_split_publics {
+			self.old_split_publics = self.split_publics.clone();
+			let keys: Vec<&str> = self.split_publics.split_whitespace().collect();
+			self.split_publics_parsed = match keys.is_empty() {
+				true  => Err("Enter the public split key part of every contributor".into()),
+				false => crate::split::publics(&keys).map_err(|e| e.to_string()),
+			};
+		}
+
+		ui.add_space(10.0);
+		ui.group(|ui| {
+			ui.label(RichText::new("Public Split Key part of every contributor (1 per line)").color(BONE)).on_hover_text(SPLIT_SEARCH);
+			ui.scope(|ui| {
+				ui.set_enabled(!self.state.iterating);
+				ui.add_sized([width, text * 2.0], TextEdit::multiline(&mut self.split_publics).hint_text("Paste public split key parts (192 characters each), e.g: `219126f1...`"));
+			});
+			match &self.split_publics_parsed {
+				Ok(publics) => { ui.label(RichText::new(format!("✔ {} public part(s)", publics.len())).color(GREEN)); },
+				Err(e)      => { ui.label(RichText::new(format!("❌ {e}")).color(RED)); },
+			}
+
+			// The pattern + settings come from the search tab.
+			let regex = self.regex();
+			ui.label(format!("Pattern | {regex} | {} (set in the Search tab)", self.network));
+			let fail = match (self.mode, self.regex_ok) {
+				(Mode::Integrated, _) => Some("Split keys find new wallets, switch the Search tab to `Standard`".to_string()),
+				(Mode::Standard, false) => Some(self.regex_fail.clone()),
+				(Mode::Standard, true) => None,
+			};
+			if let Some(fail) = fail {
+				ui.label(RichText::new(format!("❌ {fail}")).color(RED));
+			}
+
+			ui.horizontal(|ui| {
+				let w = (width / 2.0) - 5.0;
+				ui.scope(|ui| {
+					ui.set_enabled(!self.state.iterating && fail.is_none() && self.split_publics_parsed.is_ok());
+					if ui.add_sized([w, text], Button::new("Start")).on_hover_text(SPLIT_SEARCH).clicked() {
+						let publics = self.split_publics_parsed.clone().unwrap_or_default();
+						self.reset_workers();
+						self.state.threads        = self.threads;
+						self.state.network        = self.network;
+						self.state.found          = 0;
+						self.state.ignore_case    = self.ignore_case;
+						self.state.leet           = self.leet;
+						self.state.integrated     = None;
+						self.state.chance         = None;
+						self.state.likelier       = None;
+						self.estimate             = Some({
+							let (regex, target) = (regex.clone(), self.target());
+							crate::difficulty::spawn(move || (crate::difficulty::chance(&regex, target), None))
+						});
+						self.state.count          = match self.infinite {
+							true  => None,
+							false => Some(self.count),
+						};
+						self.state.pattern        = Patterns::new([&regex]).unwrap();
+						self.state.pattern_string = regex.clone();
+						self.state.iterating      = true;
+						self.state.start          = Instant::now();
+						crate::address::spawn_workers(
+							self.threads,
+							&self.to,
+							&self.state.iter,
+							&self.state.die,
+							&crate::checkpoint::positions(self.threads, None),
+							&self.state.pattern,
+							Some(crate::split::sum(&publics)),
+							self.state.random_view,
+							self.state.network,
+						);
+						self.state.split_key = Some(publics);
+						self.split_found     = None;
+					}
+				});
+				ui.scope(|ui| {
+					ui.set_enabled(self.state.iterating && self.state.split_key.is_some());
+					if ui.add_sized([w, text], Button::new("Stop")).clicked() {
+						self.state.die.store(true, std::sync::atomic::Ordering::SeqCst);
+						self.state.iterating = false;
+					}
+				});
+			});
+
+			if let Some((address, blob)) = &self.split_found {
+				ui.label(format!("Address | {address}..."));
+				copy_row(ui, width, text, "Calculated Split Key part (send this back)", blob, GREEN);
+			}
+		});
+
+		//-------------------------------------------------- Join.
+		ui.add_space(10.0);
+		ui.group(|ui| {
+			ui.set_enabled(!self.state.iterating);
+			ui.label(RichText::new("Job file (agreed with whoever searches)").color(BONE)).on_hover_text(SPLIT_JOB);
+			ui.add_sized([width, text], TextEdit::singleline(&mut self.split_job_path).hint_text("Job file path, e.g: `hinto.json`"));
+			ui.scope(|ui| {
+				ui.set_enabled(self.split_publics_parsed.is_ok() && !self.split_job_path.is_empty());
+				if ui.add_sized([width, text], Button::new("Write job file")).on_hover_text(SPLIT_JOB).clicked() {
+					self.split_job_written = Some(self.write_job());
+				}
+			});
+			match &self.split_job_written {
+				Some(Ok(path)) => { ui.label(RichText::new(format!("✔ Written to [{path}]")).color(GREEN)); },
+				Some(Err(e))   => { ui.label(RichText::new(format!("❌ {e}")).color(RED)); },
+				None           => (),
+			}
+
+			ui.label(RichText::new("Calculated Split Key part (`vanity...`)").color(BONE)).on_hover_text(SPLIT_JOIN);
+			ui.add_sized([width, text], TextEdit::singleline(&mut self.split_calculated).hint_text("Paste the part sent back by whoever searched"));
+			ui.label(RichText::new("Private Split Key part of every contributor (secret, 1 per line)").color(RED)).on_hover_text(SPLIT_JOIN);
+			ui.add_sized([width, text * 2.0], TextEdit::multiline(&mut self.split_privates).hint_text("Paste private split key parts"));
+
+			if ui.add_sized([width, text], Button::new("Join")).on_hover_text(SPLIT_JOIN).clicked() {
+				self.split_joined = Some(self.join());
+			}
+			match &self.split_joined {
+				Some(Ok([address, spend, view, seed])) => {
+					copy_row(ui, width, text, "✔ Monero Address", address, GREEN);
+					copy_row(ui, width, text, "Private Spend Key (keep secret)", spend, RED);
+					copy_row(ui, width, text, "Private View Key (keep secret)", view, RED);
+					if !seed.is_empty() {
+						copy_row(ui, width, text, "Seed (keep secret)", seed, RED);
+					}
+				},
+				Some(Err(e)) => { ui.label(RichText::new(format!("❌ {e}")).color(RED)); },
+				None => (),
+			}
+		});
+	}
+}
+
+// A label, a read-only `value` and a button that copies it.
+fn copy_row(ui: &mut egui::Ui, width: f32, text: f32, label: &str, mut value: &str, color: egui::Color32) {
+	ui.label(RichText::new(label).color(color));
+	ui.horizontal(|ui| {
+		ui.add_sized([width - 90.0, text], TextEdit::singleline(&mut value));
+		if ui.add_sized([80.0, text], Button::new("Copy")).clicked() {
+			ui.output_mut(|o| o.copied_text = value.to_string());
+		}
+	});
+}
+
 //---------------------------------------------------------------------------------------------------- `egui` event loop.
 impl eframe::App for Gui {
     //-------------------------------------------------------------------------------- On exit.
@@ -173,98 +583,347 @@ impl eframe::App for Gui {
 			ui.separator();
 			ui.add_space(10.0);
 
-			//-------------------------------------------------- User input address pattern.
-			// Regex Check.
-			if self.pattern != self.old_pattern {
-				self.old_pattern = self.pattern.clone();
-				match crate::regexes::validate(&self.pattern) {
-					Some(fail) => {
-						self.regex_ok   = false;
-						self.regex_fail = fail;
-					},
-					None => self.regex_ok = true,
-				};
-			}
+			//-------------------------------------------------- Tab.
+			ui.group(|ui| { ui.horizontal(|ui| {
+				let width = (width / 2.0) - 10.0;
+				for (tab, hover) in [(Tab::Search, SEARCH), (Tab::SplitKey, SPLIT_KEY)] {
+					if ui.add_sized([width, text], SelectableLabel::new(self.tab == tab, tab.to_string())).on_hover_text(hover).clicked() {
+						self.tab = tab;
+					}
+				}
+			})});
+			ui.add_space(10.0);
 
-			ui.horizontal(|ui| {
-				ui.set_enabled(!self.state.iterating);
+			if self.tab == Tab::SplitKey {
+				self.split_key_tab(ui, width, text);
+			} else {
+				//-------------------------------------------------- Mode.
+				ui.group(|ui| { ui.horizontal(|ui| {
+					ui.set_enabled(!self.state.iterating);
+					let width = (width / 2.0) - 10.0;
+					if ui.add_sized([width, text], SelectableLabel::new(self.mode == Mode::Standard, "Standard")).on_hover_text(STANDARD).clicked() {
+						self.mode = Mode::Standard;
+					}
+					if ui.add_sized([width, text], SelectableLabel::new(self.mode == Mode::Integrated, "Integrated")).on_hover_text(INTEGRATED).clicked() {
+						self.mode = Mode::Integrated;
+					}
+				})});
+				ui.add_space(10.0);
 
-				// TextEdit.
-				let edit = TextEdit::singleline(&mut self.pattern)
-					.hint_text("Enter address pattern, e.g: `hinto` would find an address like: `44hinto...`")
-					.desired_width(width - 25.0);
-
-				// Regex checkmark.
-				if self.regex_ok {
-					ui.add_sized([width - 25.0, text], edit);
-					ui.add_sized([5.0, text], Label::new(RichText::new("✔").color(GREEN)));
-				} else {
-					ui.add_sized([width - 25.0, text], edit).on_hover_text(self.regex_fail);
-					ui.add_sized([5.0, text], Label::new(RichText::new("❌").color(RED)));
-				};
-			});
+				//-------------------------------------------------- User input standard address (integrated).
+				if self.mode == Mode::Integrated {
+					// Address check.
+					if self.address != self.old_address {
+						self.old_address = self.address.clone();
+						self.address_parsed = match <monero::Address as std::str::FromStr>::from_str(self.address.trim()) {
+							Ok(a) if a.addr_type == monero::AddressType::Standard => Ok(a),
+							Ok(_)  => Err("Address must be a standard address"),
+							Err(_) => Err("Address is not a valid Monero address"),
+						};
+					}
 
-			//-------------------------------------------------- PatternType.
-			ui.add_space(10.0);
-			ui.group(|ui| { ui.horizontal(|ui| {
-				ui.set_enabled(!self.state.iterating);
-				let width = (width / 2.0) - 10.0;
-				if ui.add_sized([width, text], SelectableLabel::new(self.pattern_type == PatternType::Third, "Third (basic)")).on_hover_text(THIRD).clicked() {
-					self.pattern_type = PatternType::Third;
+					ui.horizontal(|ui| {
+						ui.set_enabled(!self.state.iterating);
+
+						let edit = TextEdit::singleline(&mut self.address)
+							.hint_text("Enter your wallet's standard address, e.g: `44hinto...`")
+							.desired_width(width - 25.0);
+
+						match self.address_parsed {
+							Ok(_) => {
+								ui.add_sized([width - 25.0, text], edit);
+								ui.add_sized([5.0, text], Label::new(RichText::new("✔").color(GREEN)));
+							},
+							Err(e) => {
+								ui.add_sized([width - 25.0, text], edit).on_hover_text(e);
+								ui.add_sized([5.0, text], Label::new(RichText::new("❌").color(RED)));
+							},
+						}
+					});
+					ui.add_space(10.0);
 				}
-				if ui.add_sized([width, text], SelectableLabel::new(self.pattern_type == PatternType::First, "First (advanced)")).on_hover_text(FIRST).clicked() {
-					self.pattern_type = PatternType::First;
+
+				//-------------------------------------------------- User input address pattern.
+				// Regex Check.
+				let regex = self.regex();
+				if regex != self.old_pattern || self.network != self.old_network {
+					match crate::regexes::validate_pattern(&self.pattern(), &regex, self.target()) {
+						Err(e) => {
+							self.regex_ok   = false;
+							self.regex_fail = e.to_string();
+						},
+						Ok(_) => self.regex_ok = true,
+					};
+					self.old_pattern = regex;
+					self.old_network = self.network;
 				}
-			})});
 
-			//-------------------------------------------------- Threads.
-			ui.add_space(10.0);
-			ui.scope(|ui| {
-				ui.set_enabled(!self.state.iterating);
-				let width = width - 50.0;
-				ui.spacing_mut().slider_width = width;
-				ui.add_sized([width, text], Slider::new(&mut self.threads, 1..=*THREADS_MAX));
-			});
+				ui.horizontal(|ui| {
+					ui.set_enabled(!self.state.iterating);
 
-			//-------------------------------------------------- Start/Stop.
-			ui.add_space(10.0);
-			ui.horizontal(|ui| {
-				let w = (width / 2.0) - 5.0;
+					// TextEdit.
+					let edit = TextEdit::singleline(&mut self.pattern)
+						.hint_text("Enter address pattern, e.g: `hinto` would find an address like: `44hinto...`")
+						.desired_width(width - 25.0);
+
+					// Regex checkmark.
+					if self.regex_ok {
+						ui.add_sized([width - 25.0, text], edit);
+						ui.add_sized([5.0, text], Label::new(RichText::new("✔").color(GREEN)));
+					} else {
+						ui.add_sized([width - 25.0, text], edit).on_hover_text(self.regex_fail.as_str());
+						ui.add_sized([5.0, text], Label::new(RichText::new("❌").color(RED)));
+					};
+				});
+
+				//-------------------------------------------------- PatternType.
+				ui.add_space(10.0);
+				ui.group(|ui| { ui.horizontal(|ui| {
+					ui.set_enabled(!self.state.iterating);
+					match self.mode {
+						Mode::Standard => {
+							let width = (width / 2.0) - 10.0;
+							if ui.add_sized([width, text], SelectableLabel::new(self.pattern_type == PatternType::Third, "Third (basic)")).on_hover_text(THIRD).clicked() {
+								self.pattern_type = PatternType::Third;
+							}
+						},
+						Mode::Integrated => {
+							let width = (width / 3.0) - 10.0;
+							if ui.add_sized([width, text], SelectableLabel::new(self.pattern_type == PatternType::Third && self.tail_type == TailType::Suffix, "Suffix (basic)")).on_hover_text(SUFFIX).clicked() {
+								self.pattern_type = PatternType::Third;
+								self.tail_type    = TailType::Suffix;
+							}
+							if ui.add_sized([width, text], SelectableLabel::new(self.pattern_type == PatternType::Third && self.tail_type == TailType::Infix, "Infix (basic)")).on_hover_text(INFIX).clicked() {
+								self.pattern_type = PatternType::Third;
+								self.tail_type    = TailType::Infix;
+							}
+						},
+					}
+					let width = match self.mode {
+						Mode::Standard   => (width / 2.0) - 10.0,
+						Mode::Integrated => (width / 3.0) - 10.0,
+					};
+					if ui.add_sized([width, text], SelectableLabel::new(self.pattern_type == PatternType::First, "First (advanced)")).on_hover_text(FIRST).clicked() {
+						self.pattern_type = PatternType::First;
+					}
+				})});
+
+				//-------------------------------------------------- Network.
+				ui.add_space(10.0);
+				ui.group(|ui| { ui.horizontal(|ui| {
+					// Integrated addresses use the network of the address given.
+					ui.set_enabled(!self.state.iterating && self.mode == Mode::Standard);
+					let width = (width / 3.0) - 10.0;
+					for network in [Network::Mainnet, Network::Stagenet, Network::Testnet] {
+						if ui.add_sized([width, text], SelectableLabel::new(self.network == network, network.to_string())).on_hover_text(NETWORK).clicked() {
+							self.network = network;
+						}
+					}
+				})});
+
+				//-------------------------------------------------- Threads.
+				ui.add_space(10.0);
 				ui.scope(|ui| {
-					ui.set_enabled(!self.state.iterating && self.regex_ok);
-					if ui.add_sized([w, text], Button::new("Start")).clicked() {
-						// Start.
-						let regex = match self.pattern_type {
-							PatternType::Third => format!("^..{}.*$", self.pattern),
-							PatternType::First => self.pattern.to_string(),
-						};
+					ui.set_enabled(!self.state.iterating);
+					let width = width - 50.0;
+					ui.spacing_mut().slider_width = width;
+					ui.add_sized([width, text], Slider::new(&mut self.threads, 1..=*THREADS_MAX));
+				});
 
-						self.state.die.store(false, std::sync::atomic::Ordering::SeqCst);
-						self.state.iter.store(0, std::sync::atomic::Ordering::SeqCst);
-						self.state.threads        = self.threads;
-						self.state.pattern        = Regex::new(&regex).unwrap();
-						self.state.pattern_string = regex;
-						self.state.iterating      = true;
-						self.state.start          = Instant::now();
-						crate::address::spawn_workers(
-							self.threads,
-							&self.to,
-							&self.state.iter,
-							&self.state.die,
-							&self.state.pattern,
-							None
-						);
+				//-------------------------------------------------- Count.
+				ui.add_space(10.0);
+				ui.horizontal(|ui| {
+					ui.set_enabled(!self.state.iterating);
+					ui.checkbox(&mut self.infinite, "Infinite").on_hover_text(COUNT);
+					ui.scope(|ui| {
+						ui.set_enabled(!self.infinite);
+						let width = ui.available_width() - 50.0;
+						ui.spacing_mut().slider_width = width;
+						ui.add_sized([width, text], Slider::new(&mut self.count, 1..=100)).on_hover_text(COUNT);
+					});
+				});
+
+				//-------------------------------------------------- Ignore case.
+				ui.add_space(10.0);
+				ui.horizontal(|ui| {
+					ui.set_enabled(!self.state.iterating);
+					ui.checkbox(&mut self.ignore_case, "Ignore case").on_hover_text(IGNORE_CASE);
+					ui.checkbox(&mut self.leet, "Leetspeak").on_hover_text(LEET);
+
+					// A few strings the expanded pattern would match.
+					if self.leet {
+						let samples = Leet::default().samples(&self.pattern, self.ignore_case, 4);
+						if !samples.is_empty() {
+							ui.label(RichText::new(format!("e.g: {}", samples.join(", "))).color(BONE));
+						}
 					}
 				});
-				ui.scope(|ui| {
-					ui.set_enabled(self.state.iterating);
-					if ui.add_sized([w, text], Button::new("Stop")).clicked() {
-						// Stop.
-						self.state.die.store(true, std::sync::atomic::Ordering::SeqCst);
-						self.state.iterating = false;
+
+				//-------------------------------------------------- Results.
+				ui.add_space(10.0);
+				ui.horizontal(|ui| {
+					ui.set_enabled(!self.state.iterating);
+					let w = (width / 3.0) - 10.0;
+					ui.add_sized([w, text], TextEdit::singleline(&mut self.results_path).hint_text("Results file")).on_hover_text(RESULTS);
+					ui.add_sized([w, text], TextEdit::singleline(&mut self.results_password).password(true).hint_text("Password")).on_hover_text(RESULTS);
+					ui.checkbox(&mut self.show_keys, "Show keys").on_hover_text(RESULTS);
+				});
+
+				//-------------------------------------------------- Start/Stop.
+				ui.add_space(10.0);
+				ui.horizontal(|ui| {
+					let w = (width / 2.0) - 5.0;
+					ui.scope(|ui| {
+						let address_ok = self.mode == Mode::Standard || self.address_parsed.is_ok();
+						ui.set_enabled(!self.state.iterating && self.regex_ok && address_ok && self.keys_ok());
+						if ui.add_sized([w, text], Button::new("Start")).clicked() && self.open_results() {
+							// Start.
+							let regex = self.regex();
+							self.state.integrated = match (self.mode, &self.address_parsed) {
+								(Mode::Integrated, Ok(address)) => {
+									self.network = Network::from(address.network);
+									Some((address.public_spend, address.public_view))
+								},
+								_ => None,
+							};
+
+							self.reset_workers();
+							self.state.threads        = self.threads;
+							self.state.network        = self.network;
+							self.state.tail_type      = self.tail_type;
+							self.state.found          = 0;
+							self.state.ignore_case    = self.ignore_case;
+							self.state.leet           = self.leet;
+							self.state.split_key      = None;
+							self.state.chance         = None;
+							self.state.likelier       = None;
+							self.estimate             = Some({
+								let (regex, target) = (regex.clone(), self.target());
+								let plain = (self.ignore_case || self.leet).then(|| self.wrap(&self.pattern));
+								crate::difficulty::spawn(move || {
+									let chance = crate::difficulty::chance(&regex, target);
+									let likelier = plain
+										.and_then(|plain| crate::difficulty::chance(&plain, target))
+										.filter(|plain| plain.p > 0.0)
+										.zip(chance)
+										.map(|(plain, chance)| chance.p / plain.p);
+									(chance, likelier)
+								})
+							});
+							self.state.count          = match self.infinite {
+								true  => None,
+								false => Some(self.count),
+							};
+							self.state.pattern        = Patterns::new([&regex]).unwrap();
+							self.state.pattern_string = regex;
+							self.state.iterating      = true;
+							self.state.start          = Instant::now();
+							if let Some((spend, view)) = self.state.integrated {
+								crate::address::spawn_integrated_workers(
+									self.threads,
+									&self.to,
+									&self.state.iter,
+									&self.state.die,
+									&crate::checkpoint::positions(self.threads, None),
+									&self.state.pattern,
+									spend,
+									view,
+									self.state.network,
+								);
+							} else {
+								crate::address::spawn_workers(
+									self.threads,
+									&self.to,
+									&self.state.iter,
+									&self.state.die,
+									&crate::checkpoint::positions(self.threads, None),
+									&self.state.pattern,
+									None,
+									self.state.random_view,
+									self.state.network,
+								);
+							}
+						}
+					});
+					ui.scope(|ui| {
+						ui.set_enabled(self.state.iterating);
+						if ui.add_sized([w, text], Button::new("Stop")).clicked() {
+							// Stop.
+							self.state.die.store(true, std::sync::atomic::Ordering::SeqCst);
+							self.state.iterating = false;
+
+							// A stopped queue keeps the stats of every job.
+							if let Some(mut queue) = self.queue.take() {
+								queue.stop();
+								writeln!(self.state.history, "Queue stopped");
+								for i in 0..queue.jobs.len() {
+									writeln!(self.state.history, "{}", queue.stats(i));
+								}
+								writeln!(self.state.history);
+							}
+						}
+					});
+				});
+
+				//-------------------------------------------------- Queue.
+				ui.add_space(10.0);
+				ui.group(|ui| {
+					ui.set_enabled(!self.state.iterating);
+					ui.horizontal(|ui| {
+						let w = (width / 4.0) - 10.0;
+						ui.scope(|ui| {
+							let address_ok = self.mode == Mode::Standard || self.address_parsed.is_ok();
+							ui.set_enabled(self.regex_ok && address_ok && !self.infinite);
+							if ui.add_sized([w, text], Button::new("Add to queue")).on_hover_text(QUEUE).clicked() {
+								match self.job() {
+									Ok(job) => self.jobs.push(job),
+									Err(e)  => self.regex_fail = e.to_string(),
+								}
+							}
+						});
+						for (schedule, name) in [(Schedule::Sequential, "Sequential"), (Schedule::RoundRobin, "Round-robin")] {
+							if ui.add_sized([w, text], SelectableLabel::new(self.schedule == schedule, name)).on_hover_text(SCHEDULE).clicked() {
+								self.schedule = schedule;
+							}
+						}
+						ui.scope(|ui| {
+							ui.set_enabled(self.schedule == Schedule::RoundRobin);
+							ui.spacing_mut().slider_width = w - 50.0;
+							ui.add_sized([w, text], Slider::new(&mut self.slice, 1..=600).suffix("s")).on_hover_text(SLICE);
+						});
+					});
+
+					// The queued jobs.
+					let mut remove = None;
+					for (i, job) in self.jobs.iter().enumerate() {
+						ui.horizontal(|ui| {
+							if ui.add_sized([25.0, text], Button::new("✖")).clicked() {
+								remove = Some(i);
+							}
+							ui.label(format!("{} | {} | {} | {} | x{}", i + 1, job.pattern, job.mode, job.network, job.count));
+						});
+					}
+					if let Some(i) = remove {
+						self.jobs.remove(i);
 					}
+
+					ui.scope(|ui| {
+						ui.set_enabled(!self.jobs.is_empty() && self.keys_ok());
+						if ui.add_sized([width - 15.0, text], Button::new(format!("Start queue ({} jobs)", self.jobs.len()))).clicked() && self.open_results() {
+							// Start the queue, the 1st `poll()` starts the 1st job.
+							self.state.die.store(false, std::sync::atomic::Ordering::SeqCst);
+							self.state.threads     = self.threads;
+							self.state.found       = 0;
+							self.state.ignore_case = self.ignore_case;
+							self.state.leet        = self.leet;
+							self.state.split_key   = None;
+							self.state.iterating   = true;
+							self.queue = Some(Queue::new(self.jobs.clone(), self.threads, self.schedule, Duration::from_secs(self.slice), self.state.random_view));
+							writeln!(self.state.history, "Queue | {} jobs | {}\n", self.jobs.len(), self.schedule);
+						}
+					});
 				});
-			});
+			}
 
 			//-------------------------------------------------- Stats.
 			ui.add_space(text);
@@ -278,14 +937,43 @@ impl eframe::App for Gui {
 			ui.add_space(5.0);
 
 			egui::Frame::none().fill(DARK_GRAY).show(ui, |ui| {
-				let results = format!(
-					"Speed   | {} keys per second\nTries   | {}\nElapsed | {}\nThreads | {}\nPattern | {}",
+				let mut results = format!(
+					"Speed   | {} keys per second\nTries   | {}\nElapsed | {}\nFound   | {}\nThreads | {}\nNetwork | {}\nPattern | {}",
 					Unsigned::from(self.state.speed),
 					iter,
 					self.state.elapsed,
+					Unsigned::from(self.state.found),
 					Unsigned::from(self.state.threads),
-					self.state.pattern,
+					self.state.network,
+					self.state.pattern_string,
 				);
+				if self.state.ignore_case {
+					results += "\nCase    | Ignored";
+				}
+				if self.state.leet {
+					results += "\nLeet    | On";
+				}
+				// Sampling the pattern runs in the background.
+				if self.estimate.as_ref().is_some_and(|e| e.is_finished()) {
+					if let Some(Ok((chance, likelier))) = self.estimate.take().map(std::thread::JoinHandle::join) {
+						self.state.chance   = chance;
+						self.state.likelier = likelier;
+					}
+				}
+				match (self.state.chance, self.state.likelier) {
+					(Some(chance), Some(likelier)) => results += &format!("\nChance  | {chance} (~{likelier:.1}x more likely)"),
+					(Some(chance), None)           => results += &format!("\nChance  | {chance}"),
+					(None, _) if self.state.pattern_string.is_empty() => (),
+					(None, _) if self.estimate.is_some() || self.queue.as_ref().is_some_and(Queue::estimating) => results += "\nChance  | Estimating...",
+					(None, _)                      => results += "\nChance  | Unknown (no random address matched, it may be impossible)",
+				}
+				if let Some(chance) = self.state.chance {
+					if let Some(etas) = chance.etas(self.state.speed) {
+						results += &format!("\n50/90/99% | {etas}");
+					}
+					let tries = self.state.iter.load(std::sync::atomic::Ordering::SeqCst);
+					results += &format!("\nFound by now | {:.1}%", chance.found_by(tries) * 100.0);
+				}
 				ui.add_sized([width, text], TextEdit::multiline(&mut results.as_str()));
 			});
 
@@ -315,21 +1003,138 @@ impl eframe::App for Gui {
 			});
 
 			//-------------------------------------------------- Check for message.
-			if let Ok(msg) = self.from.try_recv() {
+			// Every match is streamed to the history, hits after `count` are never handled.
+			while !self.state.count.is_some_and(|count| self.state.found >= count) {
+				let Ok(hit) = self.from.try_recv() else { break };
 				let iter = self.state.iter.load(std::sync::atomic::Ordering::SeqCst);
 
-				writeln!(
-					self.state.history,
-					"Address   | {}\nSpend Key | {}\nView Key  | {}\nSpeed     | {} keys per second\nTries     | {}\n",
-					msg.0,
-					msg.1,
-					msg.2,
-					Unsigned::from(crate::speed::calculate(&self.state.start, iter)),
-					Unsigned::from(iter),
-				);
+				match (&self.state.split_key, &hit) {
+					// The part is sent back with what it found.
+					(Some(publics), Hit::Wallet { spend, .. }) => {
+						let proof = crate::split::Proof::new(spend, publics, &self.state.pattern_string, iter, self.state.network);
+						let blob = proof.to_blob();
+						writeln!(
+							self.state.history,
+							"Address    | {}...\nSplit Key  | {}\nSpeed      | {} keys per second\nTries      | {}\n",
+							proof.address,
+							blob,
+							Unsigned::from(crate::speed::calculate(&self.state.start, iter)),
+							Unsigned::from(iter),
+						);
+						self.split_found = Some((proof.address, blob));
+					},
+					(_, Hit::Integrated { address, payment_id, .. }) => {
+						writeln!(
+							self.state.history,
+							"Address    | {}\nPayment ID | {}\nSpeed      | {} keys per second\nTries      | {}\n",
+							address,
+							payment_id,
+							Unsigned::from(crate::speed::calculate(&self.state.start, iter)),
+							Unsigned::from(iter),
+						);
+					},
+					(_, Hit::Wallet { address, spend, view, .. }) => {
+						let seed = crate::mnemonic::encode(spend, Language::English);
 
-				self.state.die.store(true, std::sync::atomic::Ordering::SeqCst);
-				self.state.iterating = false;
+						Self::keep(
+							&mut self.state.history,
+							self.results.as_mut(),
+							&format!("Address   | {address}"),
+							&format!("Spend Key | {spend}\nView Key  | {view}\nSeed      | {seed}"),
+							&format!(
+								"Speed     | {} keys per second\nTries     | {}",
+								Unsigned::from(crate::speed::calculate(&self.state.start, iter)),
+								Unsigned::from(iter),
+							),
+						);
+					},
+					// The GUI doesn't search subaddresses.
+					(_, Hit::Subaddress { .. }) => continue,
+				}
+
+				self.state.found += 1;
+				if self.state.count.is_some_and(|count| self.state.found >= count) {
+					self.state.die.store(true, std::sync::atomic::Ordering::SeqCst);
+					self.state.iterating = false;
+				}
+			}
+
+			//-------------------------------------------------- Check the queue.
+			// The stats panel follows the job being searched.
+			let mut finished = false;
+			if let Some(queue) = self.queue.as_mut() {
+				for update in queue.poll() {
+					match update {
+						Update::Switch(i) => {
+							let entry = &queue.jobs[i];
+							let now = Instant::now();
+							self.state.iter           = entry.iter.clone();
+							self.state.start          = now.checked_sub(queue.elapsed(i)).unwrap_or(now);
+							self.state.pattern_string = entry.job.regex.clone();
+							self.state.network        = entry.job.network;
+							self.state.integrated     = entry.job.integrated;
+							self.state.chance         = entry.chance;
+							self.state.likelier       = None;
+							self.state.count          = Some(entry.job.count);
+							self.state.found          = entry.found;
+						},
+						Update::Found(i, hit) => {
+							let entry = &queue.jobs[i];
+							let iter = entry.iter.load(std::sync::atomic::Ordering::SeqCst);
+							match &hit {
+								Hit::Wallet { address, spend, view, .. } => {
+									let seed = crate::mnemonic::encode(spend, Language::English);
+									Self::keep(
+										&mut self.state.history,
+										self.results.as_mut(),
+										&format!("Job       | {} ({})\nAddress   | {address}", i + 1, entry.job.regex),
+										&format!("Spend Key | {spend}\nView Key  | {view}\nSeed      | {seed}"),
+										&format!("Tries     | {}", Unsigned::from(iter)),
+									);
+								},
+								Hit::Integrated { address, payment_id, .. } => {
+									writeln!(
+										self.state.history,
+										"Job        | {} ({})\nAddress    | {}\nPayment ID | {}\nTries      | {}\n",
+										i + 1,
+										entry.job.regex,
+										address,
+										payment_id,
+										Unsigned::from(iter),
+									);
+								},
+								Hit::Subaddress { address, major, minor, .. } => {
+									writeln!(
+										self.state.history,
+										"Job        | {} ({})\nAddress    | {}\nIndex      | {}/{}\nTries      | {}\n",
+										i + 1,
+										entry.job.regex,
+										address,
+										major,
+										minor,
+										Unsigned::from(iter),
+									);
+								},
+							}
+							if queue.current == Some(i) {
+								self.state.found = entry.found;
+							}
+						},
+						Update::Done(i) => { writeln!(self.state.history, "{}\n", queue.stats(i)); },
+						Update::Finished => {
+							writeln!(self.state.history, "Queue done\n");
+							self.state.iterating = false;
+							finished = true;
+						},
+					}
+				}
+				// The chances may be estimated after the job started.
+				if let Some(i) = queue.current {
+					self.state.chance = queue.jobs[i].chance;
+				}
+			}
+			if finished {
+				self.queue = None;
 			}
 		});
 	}
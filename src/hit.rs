@@ -0,0 +1,55 @@
+//---------------------------------------------------------------------------------------------------- Use
+use monero::PrivateKey;
+
+//---------------------------------------------------------------------------------------------------- Hit
+/// What a worker thread sends to `main` when an address matches.
+///
+/// `pattern` is the index of the pattern that matched.
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub enum Hit {
+	/// A new wallet (standard, split key part and suffix searches).
+	Wallet {
+		address: String,
+		spend: PrivateKey,
+		view: PrivateKey,
+		pattern: usize,
+	},
+	/// A subaddress of an existing wallet at index `major/minor`.
+	Subaddress {
+		address: String,
+		major: u32,
+		minor: u32,
+		pattern: usize,
+	},
+	/// An integrated address of an existing wallet.
+	Integrated {
+		address: String,
+		/// Hex, 8 bytes.
+		payment_id: String,
+		pattern: usize,
+	},
+}
+
+impl Hit {
+	/// The address found.
+	pub fn address(&self) -> &str {
+		match self {
+			Self::Wallet { address, .. } | Self::Subaddress { address, .. } | Self::Integrated { address, .. } => address,
+		}
+	}
+
+	/// The index of the pattern that matched.
+	pub const fn pattern(&self) -> usize {
+		match self {
+			Self::Wallet { pattern, .. } | Self::Subaddress { pattern, .. } | Self::Integrated { pattern, .. } => *pattern,
+		}
+	}
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+//#[cfg(test)]
+//mod tests {
+//  #[test]
+//  fn __TEST__() {
+//  }
+//}
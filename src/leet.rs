@@ -0,0 +1,194 @@
+//---------------------------------------------------------------------------------------------------- Use
+use crate::encode::BASE58_CHARS;
+use crate::regexes::case_alternatives;
+use std::collections::BTreeMap;
+
+//---------------------------------------------------------------------------------------------------- Leet
+/// The default substitution table.
+///
+/// Base58 has no `0`, `O`, `I` or `l`, so
+/// these cover the usual lookalikes that exist.
+const DEFAULT: &[(char, &str)] = &[
+	('a', "a4"),
+	('b', "b8"),
+	('e', "e3"),
+	('g', "g9"),
+	('i', "i1"),
+	('l', "L1"),
+	('o', "o"),
+	('s', "s5"),
+	('t', "t7"),
+	('z', "z2"),
+];
+
+/// A leetspeak/lookalike substitution table.
+///
+/// Every character in a pattern that is in the table
+/// is turned into a class of its substitutes
+/// with [`crate::regexes::expand`].
+///
+/// `hello` -> `h[e3][L1][L1]o`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Leet {
+	table: BTreeMap<char, Vec<char>>,
+}
+
+impl Default for Leet {
+	fn default() -> Self {
+		Self {
+			table: DEFAULT.iter().map(|(c, s)| (*c, s.chars().collect())).collect(),
+		}
+	}
+}
+
+impl Leet {
+	/// Parse a substitution table.
+	///
+	/// 1 character per line, followed by `=` and its substitutes.
+	/// Whitespace is ignored, `#` is for comments.
+	/// ```text
+	/// # The letter itself must be included to still match it.
+	/// e = e3
+	/// l = L1
+	/// ```
+	pub fn from_table(table: &str) -> Result<Self, &'static str> {
+		let mut map = BTreeMap::new();
+
+		for line in table.lines().map(str::trim) {
+			if line.is_empty() || line.starts_with('#') {
+				continue;
+			}
+
+			let Some((c, substitutes)) = line.split_once('=') else {
+				return Err("Leet table line must look like: `e = e3`");
+			};
+			let mut c = c.trim().chars();
+			let (Some(c), None) = (c.next(), c.next()) else {
+				return Err("Leet table must have 1 character before `=`");
+			};
+
+			let substitutes: Vec<char> = substitutes.chars().filter(|s| !s.is_whitespace()).collect();
+			if substitutes.is_empty() {
+				return Err("Leet table must have at least 1 substitute after `=`");
+			}
+			if !substitutes.iter().all(|s| s.is_ascii() && BASE58_CHARS.contains(&(*s as u8))) {
+				return Err("Leet table substitutes must be base58 characters (not `0`, `O`, `I`, `l`)");
+			}
+
+			map.insert(c, substitutes);
+		}
+
+		match map.is_empty() {
+			true  => Err("Leet table must not be empty"),
+			false => Ok(Self { table: map }),
+		}
+	}
+
+	/// The characters `c` can be replaced with.
+	///
+	/// Characters not in the table stay as they are.
+	/// With `ignore_case`, every base58 case of the substitutes is included.
+	pub fn alternatives(&self, c: char, ignore_case: bool) -> Vec<char> {
+		let substitutes = match self.table.get(&c) {
+			Some(s) => s.clone(),
+			None    => vec![c],
+		};
+
+		if !ignore_case {
+			return substitutes;
+		}
+
+		let mut alternatives = Vec::with_capacity(substitutes.len() * 2);
+		for c in substitutes.into_iter().flat_map(case_alternatives) {
+			if !alternatives.contains(&c) {
+				alternatives.push(c);
+			}
+		}
+		alternatives
+	}
+
+	/// Up to `n` example strings the expanded `word` would match.
+	///
+	/// Empty if `word` isn't a plain word (regex).
+	pub fn samples(&self, word: &str, ignore_case: bool, n: usize) -> Vec<String> {
+		if word.is_empty() || !word.chars().all(|c| c.is_ascii_alphanumeric()) {
+			return vec![];
+		}
+
+		let options: Vec<Vec<char>> = word.chars().map(|c| {
+			let alternatives = self.alternatives(c, ignore_case);
+			match alternatives.is_empty() {
+				true  => vec![c],
+				false => alternatives,
+			}
+		}).collect();
+
+		// Count through every combination, the last character changing fastest.
+		let mut samples = Vec::with_capacity(n);
+		let mut index = vec![0; options.len()];
+		'outer: while samples.len() < n {
+			samples.push(index.iter().zip(&options).map(|(i, o)| o[*i]).collect());
+
+			for (i, o) in index.iter_mut().zip(&options).rev() {
+				*i += 1;
+				if *i < o.len() {
+					continue 'outer;
+				}
+				*i = 0;
+			}
+			break;
+		}
+
+		samples
+	}
+}
+
+/// The characters `c` can be replaced with, using an optional `leet` table.
+pub fn alternatives(leet: Option<&Leet>, c: char, ignore_case: bool) -> Vec<char> {
+	match (leet, ignore_case) {
+		(Some(leet), _) => leet.alternatives(c, ignore_case),
+		(None, true)    => case_alternatives(c),
+		(None, false)   => vec![c],
+	}
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	impl Leet {
+		fn expand(&self, pattern: &str, ignore_case: bool) -> String {
+			crate::regexes::expand(pattern, |c| self.alternatives(c, ignore_case))
+		}
+	}
+
+	#[test]
+	fn expand_default() {
+		let leet = Leet::default();
+		assert_eq!(leet.expand("hello", false), "h[e3][L1][L1]o");
+		assert_eq!(leet.expand("hinto", false), "h[i1]n[t7]o");
+		assert_eq!(leet.expand("hinto", true), "[hH][i1][nN][tT7]o");
+		assert_eq!(leet.expand("^..bob.*$", false), "^..[b8]o[b8].*$");
+	}
+
+	#[test]
+	fn table_file() {
+		let leet = Leet::from_table("# comment\n\ne = e3\no = o\n").unwrap();
+		assert_eq!(leet.expand("hello", false), "h[e3]llo");
+
+		assert!(Leet::from_table("").is_err());
+		assert!(Leet::from_table("e3").is_err());
+		assert!(Leet::from_table("ee = 3").is_err());
+		assert!(Leet::from_table("e = ").is_err());
+		assert!(Leet::from_table("o = 0").is_err());
+	}
+
+	#[test]
+	fn samples() {
+		let leet = Leet::default();
+		assert_eq!(leet.samples("bot", false, 10), ["bot", "bo7", "8ot", "8o7"]);
+		assert_eq!(leet.samples("bot", false, 2), ["bot", "bo7"]);
+		assert!(leet.samples("b.t", false, 10).is_empty());
+	}
+}
@@ -8,8 +8,25 @@ mod state;
 mod threads;
 mod regexes;
 mod pattern;
+mod mode;
+mod hit;
+mod tab;
+mod network;
 mod speed;
 mod encode;
+mod difficulty;
+mod leet;
+mod vault;
+mod checkpoint;
+mod results;
+mod cryptonight;
+mod wallet;
+mod output;
+mod daemon;
+mod queue;
+mod split;
+mod mnemonic;
+mod wordlist;
 
 fn main() {
 	// Handle `CLI`.
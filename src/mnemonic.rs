@@ -0,0 +1,229 @@
+//---------------------------------------------------------------------------------------------------- Use
+use clap::ValueEnum;
+use monero::PrivateKey;
+
+//---------------------------------------------------------------------------------------------------- Constants
+// How many words are in each word list.
+const WORDS: u64 = 1626;
+// How many words encode the private key (without the checksum word).
+const SEED_LEN: usize = 24;
+
+//---------------------------------------------------------------------------------------------------- Language
+/// The word list used for the 25-word mnemonic seed.
+///
+/// Only `English` exists for now, other lists from
+/// `monero/src/mnemonics` can be added to `wordlist.rs`.
+#[derive(ValueEnum,Clone,Copy,Debug,Default,PartialEq,Eq)]
+pub enum Language {
+	#[default]
+	English,
+}
+
+impl Language {
+	#[inline(always)]
+	/// The `1626` words of this language.
+	pub fn words(self) -> &'static [&'static str; 1626] {
+		match self {
+			Self::English => &crate::wordlist::ENGLISH,
+		}
+	}
+
+	#[inline(always)]
+	/// How many characters of a word are unique within the list.
+	///
+	/// Used for the checksum and for accepting shortened words.
+	pub const fn prefix_len(self) -> usize {
+		match self {
+			Self::English => 3,
+		}
+	}
+}
+
+impl std::fmt::Display for Language {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{:?}", self)
+	}
+}
+
+//---------------------------------------------------------------------------------------------------- Encode
+/// Encode a private spend key as the 25-word Electrum-style Monero seed.
+///
+/// Every `4` bytes (little endian `u32`) turn into `3` words,
+/// the 25th word is the checksum word.
+pub fn encode(key: &PrivateKey, language: Language) -> String {
+	let list = language.words();
+	let mut words: Vec<&str> = Vec::with_capacity(SEED_LEN + 1);
+
+	for chunk in key.as_bytes().chunks_exact(4) {
+		let x = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]) as u64;
+		let w1 = x % WORDS;
+		let w2 = (x / WORDS + w1) % WORDS;
+		let w3 = (x / WORDS / WORDS + w2) % WORDS;
+		words.push(list[w1 as usize]);
+		words.push(list[w2 as usize]);
+		words.push(list[w3 as usize]);
+	}
+
+	words.push(words[checksum_index(&words, language.prefix_len())]);
+	words.join(" ")
+}
+
+/// [`encode()`], but for the hex private key string the worker threads send.
+pub fn encode_str(key: &str, language: Language) -> Option<String> {
+	match <PrivateKey as std::str::FromStr>::from_str(key) {
+		Ok(key) => Some(encode(&key, language)),
+		Err(_)  => None,
+	}
+}
+
+//---------------------------------------------------------------------------------------------------- Decode
+/// Decode a 25-word Monero seed back into the private spend key.
+///
+/// Words may be shortened to their unique prefix, like `monero-wallet-cli` allows.
+pub fn decode(seed: &str, language: Language) -> Result<PrivateKey, &'static str> {
+	let prefix_len = language.prefix_len();
+	let seed = seed.to_lowercase();
+	let words: Vec<&str> = seed.split_whitespace().collect();
+
+	if words.len() != SEED_LEN + 1 {
+		return Err("Seed must be 25 words");
+	}
+
+	// Word -> index.
+	let mut index = [0_u64; SEED_LEN];
+	for (i, word) in words[..SEED_LEN].iter().enumerate() {
+		index[i] = match find(word, language) {
+			Some(x) => x,
+			None    => return Err("Seed contains a word not in the word list"),
+		};
+	}
+
+	// Checksum.
+	let expected = words[checksum_index(&words[..SEED_LEN], prefix_len)];
+	if prefix(expected, prefix_len) != prefix(words[SEED_LEN], prefix_len) {
+		return Err("Seed checksum word is incorrect");
+	}
+
+	// Indices -> bytes.
+	let mut bytes = [0_u8; 32];
+	for (i, w) in index.chunks_exact(3).enumerate() {
+		let (w1, w2, w3) = (w[0], w[1], w[2]);
+		let x = w1 + WORDS * ((WORDS - w1 + w2) % WORDS) + WORDS * WORDS * ((WORDS - w2 + w3) % WORDS);
+		if x > u32::MAX as u64 {
+			return Err("Seed words do not encode a valid key");
+		}
+		bytes[i * 4..i * 4 + 4].copy_from_slice(&(x as u32).to_le_bytes());
+	}
+
+	match PrivateKey::from_slice(&bytes) {
+		Ok(key) => Ok(key),
+		Err(_)  => Err("Seed does not encode a valid private key"),
+	}
+}
+
+//---------------------------------------------------------------------------------------------------- Private functions
+// The first `prefix_len` characters of a word.
+fn prefix(word: &str, prefix_len: usize) -> &str {
+	match word.char_indices().nth(prefix_len) {
+		Some((i, _)) => &word[..i],
+		None         => word,
+	}
+}
+
+// Find the index of a (possibly shortened) word.
+fn find(word: &str, language: Language) -> Option<u64> {
+	let prefix_len = language.prefix_len();
+	let list = language.words();
+
+	// Exact match first, else the unique prefix.
+	if let Some(i) = list.iter().position(|w| *w == word) {
+		return Some(i as u64);
+	}
+	if word.chars().count() < prefix_len {
+		return None;
+	}
+	let p = prefix(word, prefix_len);
+	list.iter().position(|w| prefix(w, prefix_len) == p).map(|i| i as u64)
+}
+
+// CRC32 of the concatenated word prefixes, modulo the seed length.
+fn checksum_index(words: &[&str], prefix_len: usize) -> usize {
+	let mut hasher = crc32fast::Hasher::new();
+	for word in words {
+		hasher.update(prefix(word, prefix_len).as_bytes());
+	}
+	hasher.finalize() as usize % words.len()
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::str::FromStr;
+
+	// Seeds/keys of the wallets in `monero/tests/functional_tests`.
+	const SEED_1: &str = "velvet lymph giddy number token physics poetry unquoted nibs useful sabotage limits benches lifestyle eden nitrogen anvil fewest avoid batch vials washing fences goat unquoted";
+	const SPEND_1: &str = "148d78d2aba7dbca5cd8f6abcfb0b3c009ffbdbea1ff373d50ed94d78286640e";
+	const SEED_2: &str = "peeled mixture ionic radar utopia puddle buying illness nuns gadget river spout cavernous bounced paradise drunk looking cottage jump tequila melting went winter adjust spout";
+	const ADDRESS_2: &str = "44Kbx4sJ7JDRDV5aAhLJzQCjDz2ViLRduE3ijDZu3osWKBjMGkV1XPk4pfDUMqt1Aiezvephdqm6YD19GKFD9ZcXVUTp6BW";
+	const SEED_3: &str = "dilute gutter certain antics pamphlet macro enjoy left slid guarded bogeys upload nineteen bomb jubilee enhanced irritate turnip eggs swung jukebox loudly reduce sedan slid";
+	const ADDRESS_3: &str = "46r4nYSevkfBUMhuykdK3gQ98XDqDTYW1hNLaXNvjpsJaSbNtdXh1sKMsdVgqkaihChAzEy29zEDPMR3NHQvGoZCLGwTerK";
+
+	fn address(spend: PrivateKey) -> String {
+		let view = crate::address::view_from_spend(&spend);
+		monero::Address::from_keypair(monero::Network::Mainnet, &monero::KeyPair { view, spend }).to_string()
+	}
+
+	#[test]
+	fn word_list() {
+		for language in Language::value_variants() {
+			let words = language.words();
+			// Sorted and unique prefixes, so the index is the value.
+			for w in words.windows(2) {
+				assert!(prefix(w[0], language.prefix_len()) < prefix(w[1], language.prefix_len()), "{w:?}");
+			}
+		}
+	}
+
+	#[test]
+	fn encode_known_seed() {
+		let spend = PrivateKey::from_str(SPEND_1).unwrap();
+		assert_eq!(encode(&spend, Language::English), SEED_1);
+	}
+
+	#[test]
+	fn decode_known_seeds() {
+		assert_eq!(decode(SEED_1, Language::English).unwrap().to_string(), SPEND_1);
+		assert_eq!(address(decode(SEED_2, Language::English).unwrap()), ADDRESS_2);
+		assert_eq!(address(decode(SEED_3, Language::English).unwrap()), ADDRESS_3);
+	}
+
+	#[test]
+	fn round_trip() {
+		for _ in 0..100 {
+			let (private, _) = crate::address::calculate_part_split_key();
+			let private = PrivateKey::from_str(&private).unwrap();
+			let seed = encode(&private, Language::English);
+			assert_eq!(decode(&seed, Language::English).unwrap(), private);
+		}
+	}
+
+	#[test]
+	fn decode_prefix_and_case() {
+		let short: Vec<String> = SEED_1.split_whitespace().map(|w| w[..3].to_uppercase()).collect();
+		assert_eq!(decode(&short.join(" "), Language::English).unwrap().to_string(), SPEND_1);
+	}
+
+	#[test]
+	fn decode_errors() {
+		// 24 words.
+		let seed = SEED_1.rsplit_once(' ').unwrap().0;
+		assert_eq!(decode(seed, Language::English), Err("Seed must be 25 words"));
+		// Unknown word.
+		let seed = SEED_1.replacen("velvet", "xyzzy", 1);
+		assert_eq!(decode(&seed, Language::English), Err("Seed contains a word not in the word list"));
+		// Bad checksum.
+		let seed = format!("{} abbey", SEED_1.rsplit_once(' ').unwrap().0);
+		assert_eq!(decode(&seed, Language::English), Err("Seed checksum word is incorrect"));
+	}
+}
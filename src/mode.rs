@@ -0,0 +1,27 @@
+//---------------------------------------------------------------------------------------------------- Use
+use clap::ValueEnum;
+
+//---------------------------------------------------------------------------------------------------- Mode
+/// What the `GUI` is searching for.
+#[derive(ValueEnum,Clone,Copy,Debug,Default,PartialEq,Eq)]
+pub enum Mode {
+	/// A new wallet, matching the start of the address.
+	#[default]
+	Standard,
+	/// A payment ID for an existing wallet, matching the end of the address.
+	Integrated,
+}
+
+impl std::fmt::Display for Mode {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{:?}", self)
+	}
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+//#[cfg(test)]
+//mod tests {
+//  #[test]
+//  fn __TEST__() {
+//  }
+//}
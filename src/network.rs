@@ -0,0 +1,66 @@
+//---------------------------------------------------------------------------------------------------- Use
+use clap::ValueEnum;
+
+//---------------------------------------------------------------------------------------------------- Network
+#[derive(ValueEnum,Clone,Copy,Debug,Default,PartialEq,Eq,serde::Serialize,serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Network {
+	#[default]
+	Mainnet,
+	Stagenet,
+	Testnet,
+}
+
+impl Network {
+	#[inline(always)]
+	/// The `monero-rs` equivalent.
+	pub const fn monero(self) -> monero::Network {
+		match self {
+			Self::Mainnet  => monero::Network::Mainnet,
+			Self::Stagenet => monero::Network::Stagenet,
+			Self::Testnet  => monero::Network::Testnet,
+		}
+	}
+
+	#[inline(always)]
+	/// The network byte of a standard address.
+	pub fn byte(self) -> u8 {
+		self.monero().as_u8(&monero::AddressType::Standard)
+	}
+
+	#[inline(always)]
+	/// The network byte of an integrated address.
+	pub fn integrated_byte(self) -> u8 {
+		self.monero().as_u8(&monero::AddressType::Integrated(monero::util::address::PaymentId::zero()))
+	}
+
+	#[inline(always)]
+	/// The network byte of a subaddress.
+	pub fn subaddress_byte(self) -> u8 {
+		self.monero().as_u8(&monero::AddressType::SubAddress)
+	}
+}
+
+impl From<monero::Network> for Network {
+	fn from(network: monero::Network) -> Self {
+		match network {
+			monero::Network::Mainnet  => Self::Mainnet,
+			monero::Network::Stagenet => Self::Stagenet,
+			monero::Network::Testnet  => Self::Testnet,
+		}
+	}
+}
+
+impl std::fmt::Display for Network {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{:?}", self)
+	}
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+//#[cfg(test)]
+//mod tests {
+//  #[test]
+//  fn __TEST__() {
+//  }
+//}
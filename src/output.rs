@@ -0,0 +1,321 @@
+//---------------------------------------------------------------------------------------------------- Use
+use clap::ValueEnum;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool,Ordering};
+use crate::hit::Hit;
+
+//---------------------------------------------------------------------------------------------------- Output
+/// How the CLI prints.
+///
+/// - `Text`   -> The human readable, column-aligned output + redrawn progress line
+/// - `Json`   -> 1 JSON object when exiting (the `done` event with every result, also on Ctrl+C, or an `error` event)
+/// - `Ndjson` -> 1 JSON event per line as things happen (`start`, `progress`, `result`, `done`, `error`)
+#[derive(ValueEnum,Clone,Copy,Debug,Default,PartialEq,Eq)]
+pub enum Output {
+	#[default]
+	Text,
+	Json,
+	Ndjson,
+}
+
+impl Output {
+	/// Is this `Json` or `Ndjson`?
+	pub const fn is_json(self) -> bool {
+		!matches!(self, Self::Text)
+	}
+
+	/// Print an event (JSON modes only).
+	pub fn event(self, event: &Event) {
+		if self.is_json() {
+			println!("{}", serde_json::to_string(event).expect("events always serialize"));
+		}
+	}
+
+	/// Print an event that is only streamed (`Ndjson` only).
+	pub fn stream(self, event: &Event) {
+		if self == Self::Ndjson {
+			self.event(event);
+		}
+	}
+
+	/// A flag Ctrl+C sets in `Json` mode instead of exiting,
+	/// so the `done` event with every result found so far is still printed.
+	///
+	/// The other modes printed each result already, Ctrl+C just exits.
+	pub fn interrupted(self) -> Arc<AtomicBool> {
+		let interrupted = Arc::new(AtomicBool::new(false));
+		if self == Self::Json {
+			let flag = Arc::clone(&interrupted);
+			if ctrlc::set_handler(move || flag.store(true, Ordering::SeqCst)).is_err() {
+				eprintln!("WARNING: Could not handle Ctrl+C, stopping early prints no results");
+			}
+		}
+		interrupted
+	}
+
+	/// Print an error and exit with its code.
+	///
+	/// `Text` prints to `stderr`, JSON modes print an `error` event to `stdout`.
+	pub fn fail(self, code: ExitCode, message: impl std::fmt::Display) -> ! {
+		match self {
+			Self::Text => eprintln!("ERROR: {message}"),
+			_ => self.event(&Event::Error { code: code as i32, name: code.name(), message: message.to_string() }),
+		}
+		code.exit()
+	}
+}
+
+//---------------------------------------------------------------------------------------------------- Event
+/// A JSON event, the `event` field is its name.
+#[derive(Clone,Debug,Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event {
+	/// The search started.
+	Start {
+		threads: usize,
+		network: crate::network::Network,
+		patterns: Vec<String>,
+		/// `null` is infinite.
+		count: Option<u64>,
+		/// The chance a single try matches (`null` if unknown).
+		chance: Option<f64>,
+	},
+	/// Periodic stats (every `--refresh`).
+	Progress {
+		tries: u64,
+		/// Tries per second.
+		speed: u64,
+		/// Seconds.
+		elapsed: f64,
+		found: u64,
+	},
+	/// The prefix of a `--suffix-pattern` search was found, view keys are searched next.
+	Prefix {
+		address: String,
+		tries: u64,
+		elapsed: f64,
+	},
+	/// An address was found.
+	Result(Box<Found>),
+	/// A `--job` found every address it was asked for.
+	Job {
+		/// Starts at `1`.
+		job: usize,
+		pattern: String,
+		network: crate::network::Network,
+		tries: u64,
+		speed: u64,
+		elapsed: f64,
+		found: u64,
+	},
+	/// The private/public parts of a new split key.
+	SplitKey {
+		private: String,
+		public: String,
+	},
+	/// `split-job` wrote a job file.
+	SplitJob {
+		file: PathBuf,
+		checksum: String,
+	},
+	/// `serve` is listening on `address`, requests need `token`.
+	Listen {
+		address: String,
+		token: String,
+	},
+	/// The decrypted text of a `decrypt`.
+	Decrypt {
+		text: String,
+	},
+	/// The search is finished (`Json` mode also has every result).
+	Done {
+		tries: u64,
+		speed: u64,
+		elapsed: f64,
+		found: u64,
+		#[serde(skip_serializing_if = "Option::is_none")]
+		results: Option<Vec<Found>>,
+		/// Stopped with Ctrl+C before everything asked for was found.
+		#[serde(skip_serializing_if = "std::ops::Not::not")]
+		interrupted: bool,
+	},
+	/// Something failed, the process exits with `code`.
+	Error {
+		code: i32,
+		name: &'static str,
+		message: String,
+	},
+}
+
+/// A found address.
+///
+/// Fields that don't apply to the search (or are
+/// only written to the `--encrypt` file) are left out.
+#[derive(Clone,Debug,Default,Serialize)]
+pub struct Found {
+	pub address: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub spend: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub view: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub seed: Option<String>,
+	/// The calculated part of a split key.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub split_key: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub payment_id: Option<String>,
+	/// The subaddress `[major, minor]` index.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub index: Option<[u32; 2]>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub pattern: Option<String>,
+	/// The `--job` that found it (starts at `1`).
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub job: Option<usize>,
+	pub network: crate::network::Network,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub tries: Option<u64>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub elapsed: Option<f64>,
+	/// The `--wallet-dir` wallet.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub wallet: Option<PathBuf>,
+	/// The `--encrypt` file the keys were written to.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub saved_to: Option<PathBuf>,
+}
+
+impl Found {
+	/// The `result` of a worker `hit`, private keys are left out with `saved_to` (`--encrypt`).
+	///
+	/// The calculated part of a split key is not in the `hit`, it is set by the caller.
+	pub fn new(state: &crate::state::State, hit: &Hit, tries: u64, wallet: Option<PathBuf>, saved_to: Option<PathBuf>) -> Self {
+		let mut found = Self {
+			address: hit.address().to_string(),
+			pattern: state.pattern.set.patterns().get(hit.pattern()).cloned(),
+			network: state.network,
+			tries:   Some(tries),
+			elapsed: Some(state.start.elapsed().as_secs_f64()),
+			wallet,
+			saved_to,
+			..Default::default()
+		};
+		match hit {
+			Hit::Wallet { spend, view, .. } if found.saved_to.is_none() && state.split_key.is_none() => {
+				found.spend = Some(spend.to_string());
+				found.view  = Some(view.to_string());
+				if state.suffix.is_none() && !state.random_view {
+					found.seed = Some(crate::mnemonic::encode(spend, crate::mnemonic::Language::English));
+				}
+			},
+			Hit::Wallet { .. } => (),
+			Hit::Subaddress { major, minor, .. } => found.index = Some([*major, *minor]),
+			Hit::Integrated { payment_id, .. }   => found.payment_id = Some(payment_id.clone()),
+		}
+		found
+	}
+}
+
+//---------------------------------------------------------------------------------------------------- Exit codes
+/// Every exit code, these are stable.
+///
+/// Invalid command lines exit with `2` like `clap` does.
+#[derive(Clone,Copy,Debug,PartialEq,Eq)]
+pub enum ExitCode {
+	/// Finished, every address asked for was found.
+	Ok         = 0,
+	/// The command line is invalid.
+	Usage      = 2,
+	/// A pattern is empty, has characters Monero addresses don't, or is invalid regex.
+	Pattern    = 3,
+	/// A pattern can never match.
+	Impossible = 4,
+	/// A key, split key or address is invalid.
+	Key        = 5,
+	/// A mnemonic seed is invalid.
+	Seed       = 6,
+	/// A `--pattern-file`, `--leet-file`, `--queue` or split key job/result file could not be read/written,
+	/// or has a wrong version/checksum.
+	File       = 7,
+	/// A `--checkpoint`/`--resume` file could not be written/read.
+	Checkpoint = 8,
+	/// An `--encrypt` (or `decrypt`) file could not be written/read.
+	Results    = 9,
+	/// A `--wallet-dir` wallet could not be written.
+	Wallet     = 10,
+	/// `serve` could not listen on its address.
+	Serve      = 11,
+	/// A public split key part is weak: identity, small order, torsion, non-canonical, duplicate or cancelling out.
+	WeakKey    = 12,
+	/// The search stopped unexpectedly (a bug).
+	Internal   = 13,
+}
+
+impl ExitCode {
+	/// The name used in `error` events.
+	pub const fn name(self) -> &'static str {
+		match self {
+			Self::Ok         => "ok",
+			Self::Usage      => "usage",
+			Self::Pattern    => "pattern",
+			Self::Impossible => "impossible",
+			Self::Key        => "key",
+			Self::Seed       => "seed",
+			Self::File       => "file",
+			Self::Checkpoint => "checkpoint",
+			Self::Results    => "results",
+			Self::Wallet     => "wallet",
+			Self::Serve      => "serve",
+			Self::WeakKey    => "weak_key",
+			Self::Internal   => "internal",
+		}
+	}
+
+	/// Exit the process with this code.
+	pub fn exit(self) -> ! {
+		std::process::exit(self as i32)
+	}
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	// Scripts depend on these.
+	fn exit_codes_are_stable() {
+		use ExitCode::*;
+		let codes: Vec<(i32, &str)> = [Ok, Usage, Pattern, Impossible, Key, Seed, File, Checkpoint, Results, Wallet, Serve, WeakKey, Internal]
+			.iter()
+			.map(|c| (*c as i32, c.name()))
+			.collect();
+		assert_eq!(codes, [
+			(0, "ok"), (2, "usage"), (3, "pattern"), (4, "impossible"), (5, "key"),
+			(6, "seed"), (7, "file"), (8, "checkpoint"), (9, "results"), (10, "wallet"),
+			(11, "serve"), (12, "weak_key"), (13, "internal"),
+		]);
+	}
+
+	#[test]
+	fn events() {
+		let found = Found {
+			address: "44hinto".into(),
+			spend: Some("aa".into()),
+			network: crate::network::Network::Stagenet,
+			tries: Some(5),
+			..Default::default()
+		};
+		assert_eq!(
+			serde_json::to_string(&Event::Result(Box::new(found))).unwrap(),
+			r#"{"event":"result","address":"44hinto","spend":"aa","network":"stagenet","tries":5}"#,
+		);
+		assert_eq!(
+			serde_json::to_string(&Event::Progress { tries: 1, speed: 2, elapsed: 0.5, found: 0 }).unwrap(),
+			r#"{"event":"progress","tries":1,"speed":2,"elapsed":0.5,"found":0}"#,
+		);
+	}
+}
@@ -2,7 +2,8 @@
 use clap::ValueEnum;
 
 //---------------------------------------------------------------------------------------------------- PatternType
-#[derive(ValueEnum,Clone,Copy,Debug,Default,PartialEq,Eq)]
+#[derive(ValueEnum,Clone,Copy,Debug,Default,PartialEq,Eq,serde::Serialize,serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum PatternType {
 	#[default]
 	Third,
@@ -15,6 +16,33 @@ impl std::fmt::Display for PatternType {
 	}
 }
 
+//---------------------------------------------------------------------------------------------------- TailType
+/// Where a pattern is matched in the last `18` characters
+/// of an integrated address (the part the payment ID changes).
+#[derive(ValueEnum,Clone,Copy,Debug,Default,PartialEq,Eq)]
+pub enum TailType {
+	#[default]
+	Suffix,
+	Infix,
+}
+
+impl TailType {
+	#[inline(always)]
+	/// Turn the user's pattern into the regex matched against the tail.
+	pub fn regex(self, pattern: &str) -> String {
+		match self {
+			Self::Suffix => format!("{pattern}$"),
+			Self::Infix  => pattern.to_string(),
+		}
+	}
+}
+
+impl std::fmt::Display for TailType {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{:?}", self)
+	}
+}
+
 //---------------------------------------------------------------------------------------------------- TESTS
 //#[cfg(test)]
 //mod tests {
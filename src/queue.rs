@@ -0,0 +1,613 @@
+//---------------------------------------------------------------------------------------------------- Use
+use crate::checkpoint::Positions;
+use crate::difficulty::{Chance,Target};
+use crate::hit::Hit;
+use crate::mode::Mode;
+use crate::network::Network;
+use crate::regexes::{Patterns,PatternError};
+use clap::ValueEnum;
+use monero::PublicKey;
+use std::str::FromStr;
+use std::sync::{Arc,Condvar,Mutex};
+use std::sync::atomic::{AtomicBool,AtomicU64,Ordering};
+use std::sync::mpsc::{Receiver,Sender};
+use std::time::{Duration,Instant};
+
+
+//---------------------------------------------------------------------------------------------------- Schedule
+/// How the queue moves through its jobs.
+///
+/// - `Sequential` -> Each job runs until it found `count` addresses, then the next one starts
+/// - `RoundRobin` -> Every unfinished job gets a time slice in turn
+#[derive(ValueEnum,Clone,Copy,Debug,Default,PartialEq,Eq)]
+pub enum Schedule {
+	#[default]
+	Sequential,
+	RoundRobin,
+}
+
+impl std::fmt::Display for Schedule {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{:?}", self)
+	}
+}
+
+//---------------------------------------------------------------------------------------------------- Spec
+/// A job as written with `--job` or in a `--queue` file:
+/// ```text
+/// PATTERN [count=N] [network=NETWORK] [mode=standard|integrated] [address=ADDRESS]
+/// ```
+/// `address` (the wallet's standard address) makes it an integrated job.
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub struct Spec {
+	pub pattern: String,
+	pub mode: Mode,
+	pub address: Option<String>,
+	pub count: u64,
+	/// `None` uses the `--network` default.
+	pub network: Option<Network>,
+}
+
+impl FromStr for Spec {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let mut fields = s.split_whitespace();
+		let pattern = fields.next().ok_or("Job is empty")?.to_string();
+		let (mut mode, mut address, mut count, mut network) = (None, None, 1, None);
+
+		for field in fields {
+			let (key, value) = field.split_once('=').ok_or_else(|| format!("Job field [{field}] must be `key=value`"))?;
+			match key {
+				"mode"    => mode = Some(Mode::from_str(value, true).map_err(|_| format!("Job mode [{value}] must be `standard` or `integrated`"))?),
+				"address" => address = Some(value.to_string()),
+				"network" => network = Some(Network::from_str(value, true).map_err(|_| format!("Job network [{value}] must be `mainnet`, `stagenet` or `testnet`"))?),
+				"count"   => count = match value.parse() {
+					Ok(c) if c > 0 => c,
+					_ => return Err(format!("Job count [{value}] must be a number above 0")),
+				},
+				_ => return Err(format!("Unknown job field [{key}], expected `count`, `network`, `mode` or `address`")),
+			}
+		}
+
+		let mode = match (mode, &address) {
+			(Some(Mode::Standard), Some(_)) => return Err("Job `address` is only for integrated jobs".into()),
+			(Some(Mode::Integrated), None)  => return Err("Integrated job needs the wallet's `address`".into()),
+			(Some(mode), _)                 => mode,
+			(None, Some(_))                 => Mode::Integrated,
+			(None, None)                    => Mode::Standard,
+		};
+
+		Ok(Self { pattern, mode, address, count, network })
+	}
+}
+
+//---------------------------------------------------------------------------------------------------- Job
+/// Why a job is invalid.
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub enum Error {
+	/// The pattern is empty, has characters Monero addresses don't, or is invalid regex.
+	Pattern(String),
+	/// The pattern can never match.
+	Impossible(String),
+	/// The address of an integrated job is invalid.
+	Address(String),
+}
+
+impl std::fmt::Display for Error {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Pattern(e) | Self::Impossible(e) | Self::Address(e) => write!(f, "{e}"),
+		}
+	}
+}
+
+/// A validated job.
+#[derive(Clone,Debug)]
+pub struct Job {
+	/// The pattern as entered.
+	pub pattern: String,
+	/// The full regex that is matched.
+	pub regex: String,
+	pub mode: Mode,
+	/// The public spend + view key of the wallet (integrated).
+	pub integrated: Option<(PublicKey, PublicKey)>,
+	/// How many addresses to find.
+	pub count: u64,
+	/// Integrated jobs use the network of the address.
+	pub network: Network,
+}
+
+impl Job {
+	/// Validate a job with the same rules as a single search.
+	///
+	/// `expanded` is the pattern after `ignore case`/`leet`, `regex` is the full regex.
+	pub fn new(pattern: &str, expanded: &str, regex: String, mode: Mode, address: Option<&str>, count: u64, network: Network) -> Result<Self, Error> {
+		let (integrated, network) = match (mode, address) {
+			(Mode::Integrated, Some(address)) => match monero::Address::from_str(address.trim()) {
+				Ok(a) if a.addr_type == monero::AddressType::Standard => (Some((a.public_spend, a.public_view)), Network::from(a.network)),
+				Ok(_)  => return Err(Error::Address(format!("Address must be a standard address: {address}"))),
+				Err(_) => return Err(Error::Address(format!("Address is not a valid Monero address: {address}"))),
+			},
+			(Mode::Integrated, None) => return Err(Error::Address("Integrated job needs the wallet's address".into())),
+			(Mode::Standard, _)      => (None, network),
+		};
+
+		let job = Self { pattern: pattern.to_string(), regex, mode, integrated, count, network };
+		match crate::regexes::validate_pattern(expanded, &job.regex, job.target()) {
+			Ok(_) => Ok(job),
+			Err(PatternError::Invalid(e))    => Err(Error::Pattern(format!("{e}: {pattern}"))),
+			Err(PatternError::Impossible(e)) => Err(Error::Impossible(e)),
+		}
+	}
+
+	/// What part of the address the regex is matched against.
+	pub fn target(&self) -> Target {
+		match self.mode {
+			Mode::Standard   => Target::Prefix(self.network.byte()),
+			Mode::Integrated => Target::Tail,
+		}
+	}
+}
+
+//---------------------------------------------------------------------------------------------------- Pool
+// What the pool is working on.
+#[derive(Clone,Debug)]
+struct Work {
+	to: Sender<Hit>,
+	iter: Arc<AtomicU64>,
+	die: Arc<AtomicBool>,
+	positions: Positions,
+	patterns: Patterns,
+	integrated: Option<(PublicKey, PublicKey)>,
+	random_view: bool,
+	network: Network,
+}
+
+#[derive(Debug,Default)]
+struct Slot {
+	// Bumped for every new `work`.
+	generation: u64,
+	work: Option<Work>,
+	// How many threads are still inside a `work`.
+	active: usize,
+	shutdown: bool,
+}
+
+/// Worker threads that are spawned once and reused for every job.
+///
+/// Each thread waits for new work, runs the normal search loop
+/// until the work's `die` is set, then waits again.
+#[derive(Debug)]
+pub struct Pool {
+	slot: Arc<(Mutex<Slot>, Condvar)>,
+}
+
+impl Pool {
+	/// Spawn `threads` idle workers.
+	pub fn new(threads: usize) -> Self {
+		let slot = Arc::new((Mutex::new(Slot::default()), Condvar::new()));
+		for thread in 0..threads {
+			let slot = slot.clone();
+			std::thread::spawn(move || Self::worker(&slot, thread));
+		}
+		Self { slot }
+	}
+
+	fn worker(slot: &(Mutex<Slot>, Condvar), thread: usize) {
+		let (lock, cvar) = slot;
+		let mut seen = 0;
+		loop {
+			let work = {
+				let mut slot = lock.lock().unwrap();
+				while !slot.shutdown && (slot.generation == seen || slot.work.is_none()) {
+					slot = cvar.wait(slot).unwrap();
+				}
+				if slot.shutdown {
+					return;
+				}
+				seen = slot.generation;
+				slot.active += 1;
+				slot.work.clone().expect("checked above")
+			};
+
+			match work.integrated {
+				Some((spend, view)) => crate::address::calculate_integrated(
+					work.to, work.iter, work.die, work.positions, thread, work.patterns, spend, view, work.network,
+				),
+				None => crate::address::calculate(
+					work.to, work.iter, work.die, work.positions, thread, work.patterns, None, work.random_view, work.network,
+				),
+			}
+
+			lock.lock().unwrap().active -= 1;
+			cvar.notify_all();
+		}
+	}
+
+	/// Stop the current work and wait until every thread is idle.
+	pub fn pause(&self) {
+		let (lock, cvar) = &*self.slot;
+		let mut slot = lock.lock().unwrap();
+		if let Some(work) = slot.work.take() {
+			work.die.store(true, Ordering::SeqCst);
+		}
+		while slot.active > 0 {
+			slot = cvar.wait(slot).unwrap();
+		}
+	}
+
+	// Switch every thread to `work`.
+	fn run(&self, work: Work) {
+		self.pause();
+		let (lock, cvar) = &*self.slot;
+		let mut slot = lock.lock().unwrap();
+		slot.generation += 1;
+		slot.work = Some(work);
+		cvar.notify_all();
+	}
+}
+
+impl Drop for Pool {
+	fn drop(&mut self) {
+		let (lock, cvar) = &*self.slot;
+		let mut slot = lock.lock().unwrap();
+		if let Some(work) = slot.work.take() {
+			work.die.store(true, Ordering::SeqCst);
+		}
+		slot.shutdown = true;
+		cvar.notify_all();
+	}
+}
+
+//---------------------------------------------------------------------------------------------------- Queue
+/// A queued job and its stats across every time slice.
+#[derive(Debug)]
+pub struct Entry {
+	pub job: Job,
+	/// The chance a single try matches (if known, and once [`Queue::estimated()`]).
+	pub chance: Option<Chance>,
+	/// Tries across every slice.
+	pub iter: Arc<AtomicU64>,
+	/// How many addresses were found.
+	pub found: u64,
+	// Time spent searching in previous slices.
+	elapsed: Duration,
+	patterns: Patterns,
+	// Where each worker is, so the next slice continues from there.
+	positions: Positions,
+	to: Sender<Hit>,
+	from: Receiver<Hit>,
+}
+
+impl Entry {
+	/// Did this job find `count` addresses?
+	pub fn done(&self) -> bool {
+		self.found >= self.job.count
+	}
+}
+
+/// What happened since the last [`Queue::poll()`].
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub enum Update {
+	/// Job `usize` started or got its time slice.
+	Switch(usize),
+	/// Job `usize` found an address.
+	Found(usize, Hit),
+	/// Job `usize` found `count` addresses.
+	Done(usize),
+	/// Every job is done.
+	Finished,
+}
+
+/// Jobs that share 1 [`Pool`] of worker threads.
+#[derive(Debug)]
+pub struct Queue {
+	pub jobs: Vec<Entry>,
+	pub schedule: Schedule,
+	/// How long a round-robin time slice is.
+	pub slice: Duration,
+	/// The job being searched.
+	pub current: Option<usize>,
+	/// When the queue started.
+	pub start: Instant,
+	/// How many worker threads there are.
+	pub threads: usize,
+	/// Are private view keys random instead of derived?
+	pub random_view: bool,
+	// When the current slice started.
+	since: Instant,
+	pool: Pool,
+	// The chance of every job, until they are estimated.
+	estimates: Option<std::thread::JoinHandle<Vec<Option<Chance>>>>,
+}
+
+impl Queue {
+	/// A queue of `jobs` on `threads` new workers, nothing runs until the 1st [`Queue::poll()`].
+	///
+	/// The chance of each job is estimated in the background.
+	pub fn new(jobs: Vec<Job>, threads: usize, schedule: Schedule, slice: Duration, random_view: bool) -> Self {
+		let regexes: Vec<(String, Target)> = jobs.iter().map(|job| (job.regex.clone(), job.target())).collect();
+		let estimates = crate::difficulty::spawn(move || {
+			regexes.iter().map(|(regex, target)| crate::difficulty::chance(regex, *target)).collect()
+		});
+
+		let jobs = jobs.into_iter().map(|job| {
+			let (to, from) = std::sync::mpsc::channel();
+			Entry {
+				chance: None,
+				patterns: Patterns::new([&job.regex]).expect("jobs are validated"),
+				positions: crate::checkpoint::positions(threads, None),
+				iter: Arc::new(AtomicU64::new(0)),
+				found: 0,
+				elapsed: Duration::ZERO,
+				job,
+				to,
+				from,
+			}
+		}).collect();
+
+		Self {
+			jobs,
+			schedule,
+			slice,
+			current: None,
+			start: Instant::now(),
+			threads,
+			random_view,
+			since: Instant::now(),
+			pool: Pool::new(threads),
+			estimates: Some(estimates),
+		}
+	}
+
+	/// Are the chances of the jobs still being estimated?
+	pub fn estimating(&self) -> bool {
+		self.estimates.is_some()
+	}
+
+	/// Fill in the chance of every job once they are estimated (or wait for them with `wait`).
+	///
+	/// Returns `true` if they are.
+	pub fn estimated(&mut self, wait: bool) -> bool {
+		if !wait && self.estimates.as_ref().is_some_and(|e| !e.is_finished()) {
+			return false;
+		}
+		if let Some(estimates) = self.estimates.take() {
+			let chances = estimates.join().expect("estimating never panics");
+			for (entry, chance) in self.jobs.iter_mut().zip(chances) {
+				entry.chance = chance;
+			}
+		}
+		true
+	}
+
+	/// Collect matches and move to the next job when the current one is done (or its slice is over).
+	pub fn poll(&mut self) -> Vec<Update> {
+		let mut updates = Vec::new();
+		self.estimated(false);
+
+		// A job that lost its slice may still have matches waiting.
+		for (i, entry) in self.jobs.iter_mut().enumerate() {
+			while let Ok(m) = entry.from.try_recv() {
+				if entry.done() {
+					continue;
+				}
+				entry.found += 1;
+				updates.push(Update::Found(i, m));
+				if entry.done() {
+					updates.push(Update::Done(i));
+				}
+			}
+		}
+
+		let next = self.next();
+		match self.current {
+			None if next.is_none() => (),
+			Some(i) if !self.jobs[i].done() && !self.slice_over() => (),
+			// The only unfinished job keeps going.
+			Some(i) if next == Some(i) => self.since = Instant::now(),
+			_ => updates.push(self.switch(next)),
+		}
+
+		updates
+	}
+
+	/// Stop searching, the stats are kept.
+	pub fn stop(&mut self) {
+		self.pool.pause();
+		if let Some(i) = self.current.take() {
+			self.jobs[i].elapsed += self.since.elapsed();
+		}
+	}
+
+	/// Time spent searching for job `i`.
+	pub fn elapsed(&self, i: usize) -> Duration {
+		match self.current == Some(i) {
+			true  => self.jobs[i].elapsed + self.since.elapsed(),
+			false => self.jobs[i].elapsed,
+		}
+	}
+
+	/// Tries per second of job `i`.
+	pub fn speed(&self, i: usize) -> u64 {
+		let elapsed = self.elapsed(i).as_secs_f64();
+		match elapsed > 0.0 {
+			true  => (self.jobs[i].iter.load(Ordering::SeqCst) as f64 / elapsed) as u64,
+			false => 0,
+		}
+	}
+
+	/// The stats of job `i`, for history/summaries.
+	pub fn stats(&self, i: usize) -> String {
+		let entry = &self.jobs[i];
+		format!(
+			"Job {} | {} | {} | {} | Found {}/{} | Tries {} | Speed {} keys per second | Elapsed {}",
+			i + 1,
+			entry.job.regex,
+			entry.job.mode,
+			entry.job.network,
+			entry.found,
+			entry.job.count,
+			readable::Unsigned::from(entry.iter.load(Ordering::SeqCst)),
+			readable::Unsigned::from(self.speed(i)),
+			readable::Time::from(&self.elapsed(i)),
+		)
+	}
+
+	/// Tries across every job.
+	pub fn tries(&self) -> u64 {
+		self.jobs.iter().map(|e| e.iter.load(Ordering::SeqCst)).sum()
+	}
+
+	fn slice_over(&self) -> bool {
+		self.schedule == Schedule::RoundRobin && self.since.elapsed() >= self.slice
+	}
+
+	// The next unfinished job, round-robin starts looking after the current one.
+	fn next(&self) -> Option<usize> {
+		let len = self.jobs.len();
+		let after = match self.schedule {
+			Schedule::Sequential => 0,
+			Schedule::RoundRobin => self.current.map_or(0, |i| i + 1),
+		};
+		(0..len).map(|i| (after + i) % len).find(|i| !self.jobs[*i].done())
+	}
+
+	fn switch(&mut self, next: Option<usize>) -> Update {
+		self.stop();
+		self.since = Instant::now();
+		self.current = next;
+		match next {
+			Some(i) => {
+				let entry = &self.jobs[i];
+				self.pool.run(Work {
+					to: entry.to.clone(),
+					iter: entry.iter.clone(),
+					die: Arc::new(AtomicBool::new(false)),
+					positions: entry.positions.clone(),
+					patterns: entry.patterns.clone(),
+					integrated: entry.job.integrated,
+					random_view: self.random_view,
+					network: entry.job.network,
+				});
+				Update::Switch(i)
+			},
+			None => Update::Finished,
+		}
+	}
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// A standard job that finds `count` addresses quickly.
+	fn job(pattern: &str, count: u64) -> Job {
+		Job::new(pattern, pattern, format!("^..{pattern}.*$"), Mode::Standard, None, count, Network::Stagenet).unwrap()
+	}
+
+	// Poll until every job is done.
+	fn run(queue: &mut Queue) -> Vec<Update> {
+		let mut updates = Vec::new();
+		for _ in 0..6000 {
+			updates.extend(queue.poll());
+			if updates.last() == Some(&Update::Finished) {
+				return updates;
+			}
+			std::thread::sleep(Duration::from_millis(10));
+		}
+		panic!("queue never finished");
+	}
+
+	#[test]
+	fn spec() {
+		let spec: Spec = "hinto count=2 network=stagenet".parse().unwrap();
+		assert_eq!(spec, Spec { pattern: "hinto".into(), mode: Mode::Standard, address: None, count: 2, network: Some(Network::Stagenet) });
+
+		let spec: Spec = "abc address=44xx".parse().unwrap();
+		assert_eq!((spec.mode, spec.count, spec.address.as_deref()), (Mode::Integrated, 1, Some("44xx")));
+
+		for bad in ["", "a count=0", "a count", "a mode=integrated", "a mode=standard address=44xx", "a speed=1", "a network=moon"] {
+			assert!(bad.parse::<Spec>().is_err(), "{bad}");
+		}
+	}
+
+	#[test]
+	fn job_validation() {
+		let error = |pattern: &str, regex: &str, mode, address| Job::new(pattern, pattern, regex.into(), mode, address, 1, Network::Mainnet).unwrap_err();
+		assert!(matches!(error("hintO", "^..hintO.*$", Mode::Standard, None), Error::Pattern(_)));
+		assert!(matches!(error("z", "^4z", Mode::Standard, None), Error::Impossible(_)));
+		assert!(matches!(error("a", "^..a$", Mode::Standard, None), Error::Pattern(_)));
+		assert!(matches!(error("a", "a$", Mode::Integrated, Some("not an address")), Error::Address(_)));
+		assert!(matches!(error("a", "a$", Mode::Integrated, None), Error::Address(_)));
+
+		// Integrated jobs take the network of the address.
+		let spend = crate::address::rand_priv();
+		let view = crate::address::view_from_spend(&spend);
+		let address = monero::Address::from_keypair(monero::Network::Testnet, &monero::KeyPair { view, spend }).to_string();
+		let job = Job::new("a", "a", "a$".into(), Mode::Integrated, Some(&address), 1, Network::Mainnet).unwrap();
+		assert_eq!(job.network, Network::Testnet);
+		assert!(job.integrated.is_some());
+	}
+
+	#[test]
+	fn sequential() {
+		let mut queue = Queue::new(vec![job("a", 2), job("b", 1)], 2, Schedule::Sequential, Duration::from_secs(60), false);
+		let updates = run(&mut queue);
+
+		// Job 0 runs to the end before job 1 starts.
+		let switches: Vec<&Update> = updates.iter().filter(|u| matches!(u, Update::Switch(_))).collect();
+		assert_eq!(switches, [&Update::Switch(0), &Update::Switch(1)]);
+		let done = updates.iter().position(|u| *u == Update::Done(0)).unwrap();
+		assert!(done < updates.iter().position(|u| *u == Update::Switch(1)).unwrap());
+
+		for (i, entry) in queue.jobs.iter().enumerate() {
+			assert_eq!(entry.found, entry.job.count);
+			assert!(entry.iter.load(Ordering::SeqCst) > 0);
+			assert!(queue.elapsed(i) > Duration::ZERO);
+		}
+		for update in updates {
+			if let Update::Found(i, hit) = update {
+				assert_eq!(&hit.address()[2..3], ["a", "b"][i]);
+			}
+		}
+	}
+
+	#[test]
+	// Chances are estimated in the background, nothing runs before the 1st `poll()`.
+	fn estimated() {
+		let mut queue = Queue::new(vec![job("a", 1), job("(b|cd)", 1)], 1, Schedule::Sequential, Duration::from_secs(60), false);
+		assert!(queue.estimated(true));
+		assert!(!queue.estimating());
+		assert!(!queue.jobs[0].chance.unwrap().sampled);
+		assert!(queue.jobs[1].chance.unwrap().sampled);
+		assert_eq!(queue.current, None);
+	}
+
+	#[test]
+	fn round_robin() {
+		// `hinto` is too rare to be found, so the jobs take turns until `a` is done.
+		let mut queue = Queue::new(vec![job("hinto", 1), job("a", 3)], 1, Schedule::RoundRobin, Duration::from_millis(200), false);
+		let mut updates = Vec::new();
+		for _ in 0..6000 {
+			updates.extend(queue.poll());
+			if queue.jobs[1].done() {
+				break;
+			}
+			std::thread::sleep(Duration::from_millis(10));
+		}
+		assert!(queue.jobs[1].done());
+		assert!(!queue.jobs[0].done());
+		assert_eq!(updates[..2], [Update::Switch(0), Update::Switch(1)]);
+
+		// Job 0 continues alone.
+		queue.poll();
+		queue.poll();
+		assert_eq!(queue.current, Some(0));
+		queue.stop();
+		assert_eq!(queue.current, None);
+		assert!(queue.jobs[0].iter.load(Ordering::SeqCst) > 0);
+	}
+}
@@ -7,15 +7,126 @@
 //use disk::{};
 //use std::{};
 //use std::sync::{Arc,Mutex,RwLock};
-use regex::Regex;
+use regex::{Regex,RegexSet};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool,Ordering};
+use regex_syntax::hir::{Hir,HirKind,Look,Dot};
+use crate::encode::{CHUNK,SPEND_CHARS,BASE58_CHARS};
+use crate::difficulty::Target;
+
+//---------------------------------------------------------------------------------------------------- Patterns
+/// All the address patterns of a run, checked in one go.
+#[derive(Clone,Debug)]
+pub struct Patterns {
+	/// The regexes compiled together.
+	pub set: RegexSet,
+	/// Which patterns are finished (found once with `--once`).
+	pub done: Arc<[AtomicBool]>,
+}
+
+impl Patterns {
+	/// Compile the full regexes into one set.
+	pub fn new<I, S>(regexes: I) -> Result<Self, regex::Error>
+	where
+		I: IntoIterator<Item = S>,
+		S: AsRef<str>,
+	{
+		let set = RegexSet::new(regexes)?;
+		let done = (0..set.len()).map(|_| AtomicBool::new(false)).collect();
+		Ok(Self { set, done })
+	}
+
+	#[inline(always)]
+	/// The index of the first (not done) pattern that matches `s`.
+	pub fn find(&self, s: &str) -> Option<usize> {
+		if !self.set.is_match(s) {
+			return None;
+		}
+		self.set.matches(s).into_iter().find(|i| !self.done[*i].load(Ordering::Relaxed))
+	}
+
+	/// Mark a pattern as done, returns `false` if it already was.
+	pub fn finish(&self, i: usize) -> bool {
+		!self.done[i].swap(true, Ordering::SeqCst)
+	}
+
+	/// Are all patterns done?
+	pub fn all_done(&self) -> bool {
+		self.done.iter().all(|d| d.load(Ordering::SeqCst))
+	}
+
+	/// How many `11` character blocks the longest pattern needs.
+	pub fn blocks(&self) -> usize {
+		self.set.patterns().iter().map(|p| blocks(p)).max().unwrap_or(1)
+	}
+}
+
+impl Default for Patterns {
+	fn default() -> Self {
+		Self::new([""]).unwrap()
+	}
+}
 
 //---------------------------------------------------------------------------------------------------- Regex
-/// Checks a `Regex` for Monero address validity.
+/// Why an address pattern was rejected.
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub enum PatternError {
+	/// The pattern has characters Monero addresses don't, or is invalid regex.
+	Invalid(String),
+	/// The pattern is valid, but can never match.
+	Impossible(String),
+}
+
+impl std::fmt::Display for PatternError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Invalid(e) | Self::Impossible(e) => write!(f, "{e}"),
+		}
+	}
+}
+
+/// Validate an address pattern, the CLI, GUI, queue and daemon all use this.
+///
+/// `s` is the user input (after ignore case/leet), `regex` is the full regex
+/// that will be used and `target` is the part of the address it is matched against.
+/// This returns the built `Regex` and the full regex.
 ///
-/// If `None` is returned, it is _most likely_ ok.
 /// Users can still craft impossible Regexes but
 /// this makes the obvious impossible ones go away.
-pub fn validate(s: &str) -> Option<&'static str> {
+pub fn validate_pattern(s: &str, regex: &str, target: Target) -> Result<(Regex, String), PatternError> {
+	if let Some(e) = validate(s, regex, target) {
+		return Err(PatternError::Invalid(e.into()));
+	}
+	let built = Regex::new(regex).map_err(|e| PatternError::Invalid(format!("Regex failed to build: {e}")))?;
+
+	// The tail is matched separately, the rest can
+	// only reach the characters the spend key controls.
+	if let (Target::Prefix(_), Some(n)) = (target, reach(regex)) {
+		if n > SPEND_CHARS {
+			return Err(PatternError::Impossible(format!("Address pattern [{regex}] reaches character [{n}], but only the first [{SPEND_CHARS}] are controlled by the spend key")));
+		}
+	}
+	// Without `^`, the shortest match still has to fit.
+	let shortest = regex_syntax::Parser::new().parse(regex).ok().and_then(|hir| hir.properties().minimum_len());
+	if let (Target::Prefix(_), Some(n)) = (target, shortest) {
+		if n > SPEND_CHARS {
+			return Err(PatternError::Impossible(format!("Address pattern [{regex}] needs at least [{n}] characters, but only the first [{SPEND_CHARS}] are controlled by the spend key")));
+		}
+	}
+	if let Some(i) = crate::difficulty::unreachable(regex, target) {
+		return Err(PatternError::Impossible(format!(
+			"Address pattern [{regex}] can never match, character [{}] {} can only be one of [{}]",
+			i + 1,
+			crate::difficulty::place(target),
+			crate::difficulty::reachable(target, i),
+		)));
+	}
+
+	Ok((built, regex.to_string()))
+}
+
+// The characters and anchors of a pattern.
+fn validate(s: &str, regex: &str, target: Target) -> Option<&'static str> {
 	if s.is_empty() {
 		return Some("Address pattern must not be empty");
 	} else if s.contains('I') {
@@ -31,16 +142,287 @@ pub fn validate(s: &str) -> Option<&'static str> {
 	} else if s.contains('/') {
 		return Some("Address pattern must not contain '/'");
 	}
-	match Regex::new(&s) {
-		Ok(_)  => None,
-		Err(_) => Some("Regex failed to build"),
+	if matches!(target, Target::Prefix(_)) && ends_early(regex) {
+		return Some("Address pattern must not use '$' before the end of the address (only a trailing '.*$' is allowed, use a suffix pattern for the end)");
+	}
+	None
+}
+
+/// The valid base58 versions of a character (ignoring case).
+///
+/// `I`, `O` and `l` don't exist in base58, so
+/// `i` -> `i`, `o` -> `o`, `l` -> `L` only.
+pub fn case_alternatives(c: char) -> Vec<char> {
+	let mut alternatives = Vec::with_capacity(2);
+	for c in [c.to_ascii_lowercase(), c.to_ascii_uppercase()] {
+		if c.is_ascii() && BASE58_CHARS.contains(&(c as u8)) && !alternatives.contains(&c) {
+			alternatives.push(c);
+		}
+	}
+	alternatives
+}
+
+/// Replace every letter/number of a pattern with a class of its `alternatives`.
+///
+/// With [`case_alternatives`]: `hinto` -> `[hH]i[nN][tT]o`
+///
+/// Escaped characters (`\d`), repetitions (`{3}`)
+/// and classes (`[a-c]`) are kept as-is, so are
+/// characters without any alternatives.
+pub fn expand(pattern: &str, alternatives: impl Fn(char) -> Vec<char>) -> String {
+	let mut out = String::with_capacity(pattern.len() * 4);
+	let mut chars = pattern.chars();
+	let mut class = false;
+	let mut repeat = false;
+
+	while let Some(c) = chars.next() {
+		match c {
+			'\\' => {
+				out.push(c);
+				if let Some(c) = chars.next() {
+					out.push(c);
+				}
+				continue;
+			},
+			'[' if !class => class = true,
+			']' if class  => class = false,
+			'{' if !class => repeat = true,
+			'}' if repeat => repeat = false,
+			_ => (),
+		}
+
+		if !c.is_ascii_alphanumeric() || repeat || class {
+			out.push(c);
+			continue;
+		}
+
+		let alternatives = alternatives(c);
+		match alternatives.len() {
+			0 => out.push(c),
+			1 => out.push(alternatives[0]),
+			_ => {
+				out.push('[');
+				out.extend(alternatives);
+				out.push(']');
+			},
+		}
+	}
+
+	out
+}
+
+/// How many characters from the start of the address a regex needs.
+///
+/// `None` if it isn't anchored with `^` or has no upper bound, see [`blocks()`].
+///
+/// A trailing `.*` and `$` are ignored, so `^..hinto.*$` needs `7` characters.
+pub fn reach(regex: &str) -> Option<usize> {
+	let hir = regex_syntax::Parser::new().parse(regex).ok()?;
+	if !hir.properties().look_set_prefix().contains(Look::Start) {
+		return None;
+	}
+	longest(&hir)
+}
+
+/// How many `11` character blocks of the address a regex needs.
+///
+/// This is between `1` (the fast path) and `4` (the whole public spend key).
+/// A regex without `^` can match anywhere, so it gets every block unless its
+/// longest match fits in `11` characters. So does one without an upper bound.
+pub fn blocks(regex: &str) -> usize {
+	let all = SPEND_CHARS / CHUNK;
+	if let Some(n) = reach(regex) {
+		return n.div_ceil(CHUNK).clamp(1, all);
+	}
+	match regex_syntax::Parser::new().parse(regex).ok().as_ref().and_then(longest) {
+		Some(n) if n <= CHUNK => 1,
+		_ => all,
+	}
+}
+
+// The longest match of a regex, a trailing `.*` and `$` are ignored.
+fn longest(hir: &Hir) -> Option<usize> {
+	match hir.kind() {
+		HirKind::Concat(subs) => {
+			let mut subs = subs.as_slice();
+			while let Some((last, rest)) = subs.split_last() {
+				if !is_any_tail(last) {
+					break;
+				}
+				subs = rest;
+			}
+			subs.iter().try_fold(0, |sum, sub| Some(sum + max_chars(sub)?))
+		},
+		_ => max_chars(hir),
+	}
+}
+
+/// Does a regex use `$` anywhere but after a trailing `.*`?
+///
+/// Prefix workers only encode the first characters of the address,
+/// so a `$` there would match the end of those instead of the address.
+pub fn ends_early(regex: &str) -> bool {
+	let Ok(hir) = regex_syntax::Parser::new().parse(regex) else {
+		return false;
+	};
+	let subs = match hir.kind() {
+		HirKind::Concat(subs) => subs.as_slice(),
+		_ => std::slice::from_ref(&hir),
+	};
+	let subs = match subs.split_last() {
+		Some((last, rest)) if matches!(last.kind(), HirKind::Look(Look::End)) && rest.last().is_some_and(is_any) => rest,
+		_ => subs,
+	};
+	subs.iter().any(|sub| {
+		let looks = sub.properties().look_set();
+		looks.contains(Look::End) || looks.contains(Look::EndLF)
+	})
+}
+
+// Is this a trailing `.*` or `$`, which never decides a match?
+fn is_any_tail(hir: &Hir) -> bool {
+	matches!(hir.kind(), HirKind::Look(Look::End)) || is_any(hir)
+}
+
+// Is this a `.*`?
+fn is_any(hir: &Hir) -> bool {
+	match hir.kind() {
+		HirKind::Repetition(r) => {
+			r.min == 0 && r.max.is_none() &&
+			(*r.sub == Hir::dot(Dot::AnyCharExceptLF) || *r.sub == Hir::dot(Dot::AnyChar))
+		},
+		_ => false,
+	}
+}
+
+// The maximum amount of characters a regex can match, `None` if unbounded.
+fn max_chars(hir: &Hir) -> Option<usize> {
+	match hir.kind() {
+		HirKind::Empty | HirKind::Look(_) => Some(0),
+		HirKind::Literal(l)     => Some(String::from_utf8_lossy(&l.0).chars().count()),
+		HirKind::Class(_)       => Some(1),
+		HirKind::Capture(c)     => max_chars(&c.sub),
+		HirKind::Repetition(r)  => Some(r.max? as usize * max_chars(&r.sub)?),
+		HirKind::Concat(subs)   => subs.iter().try_fold(0, |sum, sub| Some(sum + max_chars(sub)?)),
+		HirKind::Alternation(subs) => subs.iter().try_fold(0, |max, sub| Some(usize::max(max, max_chars(sub)?))),
 	}
 }
 
 //---------------------------------------------------------------------------------------------------- TESTS
-//#[cfg(test)]
-//mod tests {
-//  #[test]
-//  fn __TEST__() {
-//  }
-//}
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn reach_of_patterns() {
+		assert_eq!(reach("^..hinto.*$"), Some(7));
+		assert_eq!(reach("^..hintohintohinto.*$"), Some(17));
+		assert_eq!(reach("^.(4|8)h(i|1)nto.*$"), Some(7));
+		assert_eq!(reach("^..(abc|abcdefghijk)"), Some(13));
+		assert_eq!(reach("^..a{3}b?"), Some(6));
+		assert_eq!(reach("^..a*b"), None);
+		assert_eq!(reach("hinto"), None);
+		assert_eq!(reach("^..hinto$"), Some(7));
+	}
+
+	#[test]
+	fn blocks_of_patterns() {
+		assert_eq!(blocks("^..hinto.*$"), 1);
+		assert_eq!(blocks("^..hintohinto.*$"), 2);
+		assert_eq!(blocks(&format!("^{}.*$", ".".repeat(33))), 3);
+		assert_eq!(blocks(&format!("^{}.*$", ".".repeat(44))), 4);
+		assert_eq!(blocks("hinto"), 1);
+		assert_eq!(blocks("hinto.*$"), 1);
+	}
+
+	#[test]
+	// Without `^` (or an upper bound), a match past the 11th character must still be found.
+	fn blocks_of_unanchored_patterns() {
+		assert_eq!(blocks("hintojanaixyz"), 4);
+		assert_eq!(blocks(".{11}a"), 4);
+		assert_eq!(blocks("(hinto|hintojanaixyz)"), 4);
+		assert_eq!(blocks("a.*b"), 4);
+		assert_eq!(blocks("^..a*b"), 4);
+	}
+
+	#[test]
+	fn patterns_find_and_finish() {
+		let patterns = Patterns::new(["^..a", "^..b", "^..ab"]).unwrap();
+		assert_eq!(patterns.find("44abc"), Some(0));
+		assert_eq!(patterns.find("44bcd"), Some(1));
+		assert_eq!(patterns.find("44ccc"), None);
+
+		// Done patterns are skipped.
+		assert!(patterns.finish(0));
+		assert!(!patterns.finish(0));
+		assert_eq!(patterns.find("44abc"), Some(2));
+		assert!(!patterns.all_done());
+		assert!(patterns.finish(1) && patterns.finish(2));
+		assert!(patterns.all_done());
+	}
+
+	#[test]
+	fn patterns_blocks() {
+		assert_eq!(Patterns::new(["^..a.*$", "^..hintohintoh.*$"]).unwrap().blocks(), 2);
+	}
+
+	#[test]
+	fn ignore_case_expansion() {
+		assert_eq!(expand("hinto", case_alternatives), "[hH]i[nN][tT]o");
+		assert_eq!(expand("HINTO", case_alternatives), "[hH]i[nN][tT]o");
+		assert_eq!(expand("lol", case_alternatives), "LoL");
+		assert_eq!(expand("a\\db{2}", case_alternatives), "[aA]\\d[bB]{2}");
+		assert_eq!(expand("[a-c]x", case_alternatives), "[a-c][xX]");
+		assert_eq!(expand("1.9", case_alternatives), "1.9");
+	}
+
+	#[test]
+	// Expanded patterns never contain characters base58 doesn't have.
+	fn ignore_case_valid() {
+		for c in ('a'..='z').chain('A'..='Z') {
+			let expanded = expand(&c.to_string(), case_alternatives);
+			assert_eq!(validate(&expanded, &expanded, Target::Prefix(18)), None, "{c}");
+		}
+	}
+
+	#[test]
+	fn validate_reach() {
+		let ok = format!("^{}", ".".repeat(44));
+		assert!(validate_pattern(".", &ok, Target::Prefix(18)).is_ok());
+		let too_far = format!("^{}", ".".repeat(45));
+		assert!(matches!(validate_pattern(".", &too_far, Target::Prefix(18)), Err(PatternError::Impossible(_))));
+
+		// Unanchored, the shortest match decides.
+		assert!(validate_pattern(".", &".".repeat(44), Target::Prefix(18)).is_ok());
+		let too_long = ".".repeat(45);
+		assert!(matches!(validate_pattern(".", &too_long, Target::Prefix(18)), Err(PatternError::Impossible(_))));
+		assert!(validate_pattern(".", &format!("a|{too_long}"), Target::Prefix(18)).is_ok());
+	}
+
+	#[test]
+	fn validate_patterns() {
+		let prefix = Target::Prefix(18);
+		let (regex, string) = validate_pattern("hinto", "^..hinto.*$", prefix).unwrap();
+		assert_eq!(regex.as_str(), "^..hinto.*$");
+		assert_eq!(string, "^..hinto.*$");
+		assert!(matches!(validate_pattern("", "^...*$", prefix), Err(PatternError::Invalid(_))));
+		assert!(matches!(validate_pattern("hintO", "^..hintO.*$", prefix), Err(PatternError::Invalid(_))));
+		assert!(matches!(validate_pattern("(", "^..(.*$", prefix), Err(PatternError::Invalid(_))));
+		assert!(matches!(validate_pattern("z", "^4z", prefix), Err(PatternError::Impossible(_))));
+		assert!(validate_pattern("hinto", "hinto$", Target::Tail).is_ok());
+	}
+
+	#[test]
+	// `$` is the end of the address, not of the characters prefix workers encode.
+	fn validate_end() {
+		let prefix = Target::Prefix(18);
+		assert_eq!(validate("hinto", "^..hinto.*$", prefix), None);
+		assert_eq!(validate("hinto", "^..hinto", prefix), None);
+		assert!(validate("hinto", "^..hinto$", prefix).is_some());
+		assert!(validate("hinto", &format!("^{}hinto$", ".".repeat(6)), prefix).is_some());
+		assert!(validate("hinto", "hinto$", prefix).is_some());
+		assert!(validate("hinto", "^..(hinto$|abc)", prefix).is_some());
+		// Suffix patterns are matched against the real end.
+		assert_eq!(validate("hinto", "hinto$", Target::Tail), None);
+	}
+}
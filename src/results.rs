@@ -0,0 +1,90 @@
+//---------------------------------------------------------------------------------------------------- Use
+use crate::vault::Vault;
+use std::path::{Path,PathBuf};
+
+//---------------------------------------------------------------------------------------------------- Results
+/// Found results, kept only in a password-encrypted file.
+///
+/// Every result is appended and the whole file is re-sealed,
+/// so private keys never touch the disk (or terminal) in plain text.
+pub struct Results {
+	path: PathBuf,
+	vault: Vault,
+	text: String,
+}
+
+// The results themselves are never printed.
+impl std::fmt::Debug for Results {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("Results").field("path", &self.path).finish_non_exhaustive()
+	}
+}
+
+impl Results {
+	/// Open the results file at `path`, creating it if it doesn't exist.
+	///
+	/// Existing results are kept, so `password` must be the one it was made with.
+	pub fn open(path: &Path, password: &str) -> Result<Self, &'static str> {
+		let (vault, text) = match std::fs::read(path) {
+			Ok(data) => {
+				let (vault, text) = Vault::open(password, &data)?;
+				(vault, String::from_utf8(text).map_err(|_| "Results file is not text")?)
+			},
+			Err(e) if e.kind() == std::io::ErrorKind::NotFound => (Vault::new(password)?, String::new()),
+			Err(_) => return Err("Could not read results file"),
+		};
+
+		let this = Self { path: path.to_path_buf(), vault, text };
+		this.write()?;
+		Ok(this)
+	}
+
+	/// Where the results are written.
+	pub fn path(&self) -> &Path {
+		&self.path
+	}
+
+	/// Append a result and re-write the file.
+	pub fn add(&mut self, result: &str) -> Result<(), &'static str> {
+		self.text.push_str(result);
+		self.text.push_str("\n\n");
+		self.write()
+	}
+
+	fn write(&self) -> Result<(), &'static str> {
+		crate::vault::write_atomic(&self.path, &self.vault.seal(self.text.as_bytes()))
+			.map_err(|_| "Could not write results file")
+	}
+}
+
+/// Decrypt any file written by `monero-vanity` (results/checkpoints).
+pub fn decrypt(path: &Path, password: &str) -> Result<String, &'static str> {
+	let data = std::fs::read(path).map_err(|_| "Could not read file")?;
+	let (_, text) = Vault::open(password, &data)?;
+	Ok(String::from_utf8_lossy(&text).into_owned())
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn append_and_decrypt() {
+		let path = std::env::temp_dir().join(format!("monero-vanity-results-{}", std::process::id()));
+		let _ = std::fs::remove_file(&path);
+
+		let mut results = Results::open(&path, "hunter2").unwrap();
+		results.add("Private Spend Key | a").unwrap();
+		assert!(!std::fs::read_to_string(&path).unwrap_or_default().contains("Private"));
+
+		// Re-opening keeps the old results.
+		assert!(Results::open(&path, "hunter3").is_err());
+		let mut results = Results::open(&path, "hunter2").unwrap();
+		results.add("Private Spend Key | b").unwrap();
+
+		let text = decrypt(&path, "hunter2").unwrap();
+		std::fs::remove_file(&path).unwrap();
+		assert_eq!(text, "Private Spend Key | a\n\nPrivate Spend Key | b\n\n");
+	}
+}
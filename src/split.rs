@@ -0,0 +1,692 @@
+//---------------------------------------------------------------------------------------------------- Use
+use crate::encode::SPEND_CHARS;
+use crate::network::Network;
+use crate::pattern::PatternType;
+use curve25519_dalek::constants::ED25519_BASEPOINT_TABLE;
+use curve25519_dalek::edwards::EdwardsPoint;
+use curve25519_dalek::scalar::Scalar;
+use monero::{PrivateKey,PublicKey};
+use serde::{Serialize,Deserialize};
+use std::path::{Path,PathBuf};
+use std::str::FromStr;
+
+//---------------------------------------------------------------------------------------------------- Constants
+/// What every calculated split key result starts with.
+pub const PREFIX: &str = "vanity";
+
+/// The version of the job/result file format.
+pub const FILE_VERSION: u32 = 1;
+
+/// How many hex characters a public split key part is, `key || R || s`.
+pub const PART_LEN: usize = 192;
+
+// Separates the proof of possession hash from every other `Hs()` in Monero.
+const DOMAIN: &[u8] = b"monero-vanity split key part";
+
+//---------------------------------------------------------------------------------------------------- Error
+/// Why a public split key part or calculated split key result is refused.
+#[derive(Clone,Debug,PartialEq,Eq)]
+pub enum Error {
+	/// It is not a calculated split key result.
+	Format(&'static str),
+	/// The checksum does not match its fields, it was corrupted on the way.
+	Corrupted,
+	/// A public split key part is not a valid point.
+	Invalid(String),
+	/// A public split key part has no valid proof that its private part is known.
+	Possession(String),
+	/// A public split key part is a non-canonical encoding of a point.
+	NonCanonical(String),
+	/// A public split key part is the identity point, adding it changes nothing.
+	Identity(String),
+	/// A public split key part is a small order point, adding it is (almost) nothing.
+	SmallOrder(String),
+	/// A public split key part has a torsion component, it is not in the prime order subgroup.
+	Torsion(String),
+	/// The same public split key part is in there twice.
+	Duplicate(String),
+	/// The public split key parts cancel each other out.
+	Cancelled,
+	/// A private part is not one of the public split key parts it was calculated for.
+	Public(usize),
+	/// There is no private part of this public split key part.
+	Missing(String),
+	/// `part·G + sum(publics)` is not the public spend key it claims.
+	Spend,
+	/// The address is not the one `part·G + sum(publics)` encodes to.
+	Address,
+	/// The joined address does not match the requester's pattern.
+	Pattern(String),
+	/// A job/result file could not be read/written.
+	File(String),
+	/// A job/result file is of another (newer) version.
+	Version(u32),
+	/// A job/result file was changed or corrupted.
+	Checksum,
+	/// A calculated part (or result file) is not for the requester's job.
+	Mismatch(&'static str),
+}
+
+impl std::fmt::Display for Error {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Format(e)       => write!(f, "Calculated split key is invalid: {e}"),
+			Self::Corrupted       => write!(f, "Calculated split key checksum does not match, it was corrupted on the way"),
+			Self::Invalid(k)      => write!(f, "Public split key part [{k}] is not a valid point"),
+			Self::Possession(k)   => write!(f, "Public split key part [{k}] has no valid proof of possession, generate parts with `--gen-private-split-key`"),
+			Self::NonCanonical(k) => write!(f, "Public split key part [{k}] is a non-canonical encoding, a real public key never is"),
+			Self::Identity(k)     => write!(f, "Public split key part [{k}] is the identity point, whoever calculates would know the private key"),
+			Self::SmallOrder(k)   => write!(f, "Public split key part [{k}] is a small order point, whoever calculates would (almost) know the private key"),
+			Self::Torsion(k)      => write!(f, "Public split key part [{k}] has a torsion component, it is not in the prime order subgroup"),
+			Self::Duplicate(k)    => write!(f, "Public split key part [{k}] is entered more than once"),
+			Self::Cancelled       => write!(f, "Public split key parts add up to the identity point"),
+			Self::Public(i)       => write!(f, "Private part [{}] is not one of the public split key parts the calculated part was made for", i + 1),
+			Self::Missing(k)      => write!(f, "Private part of public split key part [{k}] is missing, every contributor's private part is needed"),
+			Self::Spend           => write!(f, "Calculated split key part does not add up to the public spend key it claims"),
+			Self::Address         => write!(f, "Calculated split key address is not the address of its public spend key"),
+			Self::Pattern(p)      => write!(f, "Joined address does not match the pattern [{p}]"),
+			Self::File(e)         => write!(f, "{e}"),
+			Self::Version(v)      => write!(f, "File version [{v}] is not supported, only version [{FILE_VERSION}] is"),
+			Self::Checksum        => write!(f, "File checksum does not match, it was changed or corrupted"),
+			Self::Mismatch(e)     => write!(f, "Calculated split key is not for this job: {e}"),
+		}
+	}
+}
+
+//---------------------------------------------------------------------------------------------------- Public parts
+/// The public split key part of `private` to give out: its public key
+/// followed by a Schnorr proof of possession `R || s` of the private key.
+pub fn part(private: &PrivateKey) -> String {
+	// R = r·G, s = r + Hs(DOMAIN || R || K)·k
+	let key = &private.scalar * &ED25519_BASEPOINT_TABLE;
+	let r = crate::address::rand_priv().scalar;
+	let big_r = &r * &ED25519_BASEPOINT_TABLE;
+	let s = r + challenge(&big_r, &key) * private.scalar;
+	format!("{}{}{}", PublicKey { point: key.compress() }, PublicKey { point: big_r.compress() }, PrivateKey { scalar: s })
+}
+
+/// Parse the public split key parts of every contributor.
+///
+/// Every part must prove its private key is known, or a contributor could enter
+/// `X - sum(others)` after seeing the others and pick the final key alone.
+/// Every key must be a canonically encoded point in the prime order subgroup.
+/// The identity and small order points would let whoever calculates the last
+/// part (almost) know the private key, and points with a torsion component
+/// make the joined key differ from what `part·G` implies.
+pub fn publics<S: AsRef<str>>(parts: &[S]) -> Result<Vec<EdwardsPoint>, Error> {
+	points(parts, public)
+}
+
+/// Validate 1 public split key part, its key + proof of possession, see [`publics()`].
+pub fn public(part: &str) -> Result<EdwardsPoint, Error> {
+	let hex = part.get(..64).ok_or_else(|| Error::Invalid(part.to_string()))?;
+	let public = key(hex)?;
+
+	// s·G == R + Hs(DOMAIN || R || K)·K
+	let (big_r, s) = match (part.len() == PART_LEN, part.get(64..128), part.get(128..)) {
+		(true, Some(r), Some(s)) => (point(r), PrivateKey::from_str(s).ok()),
+		_ => (None, None),
+	};
+	match big_r.zip(s) {
+		Some((big_r, s)) if &s.scalar * &ED25519_BASEPOINT_TABLE == big_r + challenge(&big_r, &public) * public => Ok(public),
+		_ => Err(Error::Possession(hex.to_string())),
+	}
+}
+
+/// Validate 1 public key (without a proof of possession), see [`publics()`].
+pub fn key(key: &str) -> Result<EdwardsPoint, Error> {
+	let error = |e: fn(String) -> Error| e(key.to_string());
+	let compressed = PublicKey::from_str(key).map_err(|_| error(Error::Invalid))?.point;
+	let point = compressed.decompress().ok_or_else(|| error(Error::Invalid))?;
+	if point.compress() != compressed {
+		return Err(error(Error::NonCanonical));
+	}
+	if point.is_identity() {
+		return Err(error(Error::Identity));
+	}
+	if point.is_small_order() {
+		return Err(error(Error::SmallOrder));
+	}
+	if !point.is_torsion_free() {
+		return Err(error(Error::Torsion));
+	}
+	Ok(point)
+}
+
+/// Every public split key part added together, the base point of the search.
+pub fn sum(publics: &[EdwardsPoint]) -> EdwardsPoint {
+	publics.iter().sum()
+}
+
+// Parse every part with `parse`, refusing duplicates and parts that cancel each other out.
+fn points<S: AsRef<str>>(parts: &[S], parse: fn(&str) -> Result<EdwardsPoint, Error>) -> Result<Vec<EdwardsPoint>, Error> {
+	let mut points: Vec<EdwardsPoint> = Vec::with_capacity(parts.len());
+	for part in parts {
+		let part = part.as_ref();
+		let point = parse(part)?;
+		if points.contains(&point) {
+			return Err(Error::Duplicate(part.to_string()));
+		}
+		points.push(point);
+	}
+	if sum(&points).is_identity() {
+		return Err(Error::Cancelled);
+	}
+	Ok(points)
+}
+
+// Hs(DOMAIN || R || K)
+fn challenge(big_r: &EdwardsPoint, key: &EdwardsPoint) -> Scalar {
+	let mut data = DOMAIN.to_vec();
+	data.extend_from_slice(big_r.compress().as_bytes());
+	data.extend_from_slice(key.compress().as_bytes());
+	monero::cryptonote::hash::Hash::hash_to_scalar(&data).scalar
+}
+
+// The public keys of `parts` (without their proof of possession), lowercased and sorted.
+//
+// Job files keep the parts as they were typed, which can be uppercase hex.
+fn keys(parts: &[String]) -> Vec<String> {
+	let mut keys: Vec<String> = parts.iter().map(|part| part.get(..64).unwrap_or(part).to_lowercase()).collect();
+	keys.sort_unstable();
+	keys
+}
+
+//---------------------------------------------------------------------------------------------------- Proof
+/// What a `--calculate-split-key` helper sends back.
+///
+/// The calculated private part, what it was calculated for and what it found.
+/// The checksum only catches corruption: anyone with the blob has the part and
+/// can re-make it, so [`Proof::join()`] trusts none of the fields and checks the
+/// joined address against the requester's own job instead.
+#[derive(Clone,Debug,PartialEq,Eq,Serialize,Deserialize)]
+pub struct Proof {
+	/// The calculated private part.
+	pub part: String,
+	/// The public key of every contributor's part it was calculated for.
+	pub publics: Vec<String>,
+	/// The public spend key of the joined key, `part·G + sum(publics)`.
+	pub spend: String,
+	/// The first 44 characters of the joined address (the only ones the spend key decides).
+	pub address: String,
+	/// The regex the helper says matched, never trusted when joining.
+	pub pattern: String,
+	pub network: Network,
+	/// How many tries it took.
+	pub tries: u64,
+	/// The first 4 bytes of `Keccak256` of the proof with an empty checksum.
+	pub checksum: String,
+}
+
+impl Proof {
+	/// What `part` found for `publics`.
+	pub fn new(part: &PrivateKey, publics: &[EdwardsPoint], pattern: &str, tries: u64, network: Network) -> Self {
+		let spend = &part.scalar * &ED25519_BASEPOINT_TABLE + sum(publics);
+		let mut proof = Self {
+			part: part.to_string(),
+			publics: publics.iter().map(|p| PublicKey { point: p.compress() }.to_string()).collect(),
+			spend: PublicKey { point: spend.compress() }.to_string(),
+			address: address(network, &spend),
+			pattern: pattern.to_string(),
+			network,
+			tries,
+			checksum: String::new(),
+		};
+		proof.checksum = checksum(&proof);
+		proof
+	}
+
+	/// The `vanity...` blob that is sent back.
+	pub fn to_blob(&self) -> String {
+		let json = serde_json::to_vec(self).expect("proofs always serialize");
+		format!("{PREFIX}{}", base58_monero::encode(&json).expect("base58 encodes any bytes"))
+	}
+
+	/// Read a `vanity...` blob.
+	pub fn from_blob(blob: &str) -> Result<Self, Error> {
+		let base58 = blob.trim().strip_prefix(PREFIX).ok_or(Error::Format("it must start with `vanity`"))?;
+		let json = base58_monero::decode(base58).map_err(|_| Error::Format("it is not base58"))?;
+		serde_json::from_slice(&json).map_err(|_| Error::Format("it is missing fields"))
+	}
+
+	/// Check the checksum and that `part·G + sum(publics)` really is the spend key + address it claims.
+	pub fn verify(&self) -> Result<(), Error> {
+		if self.checksum != checksum(self) {
+			return Err(Error::Corrupted);
+		}
+		let part    = PrivateKey::from_str(&self.part).map_err(|_| Error::Format("the part is not a valid scalar"))?;
+		let publics = points(&self.publics, key)?;
+		let spend   = point(&self.spend).ok_or(Error::Format("the public spend key is not a valid point"))?;
+
+		if &part.scalar * &ED25519_BASEPOINT_TABLE + sum(&publics) != spend {
+			return Err(Error::Spend);
+		}
+		if address(self.network, &spend) != self.address {
+			return Err(Error::Address);
+		}
+		Ok(())
+	}
+
+	/// [`Proof::verify()`] that it is for the requester's `job` and exactly the public keys of `privates`, then join them.
+	///
+	/// Every contributor's private part is needed, a public part nobody
+	/// has the private part of (e.g. made to cancel the others out) is refused.
+	/// The helper's own pattern is not trusted: the joined address must
+	/// match the pattern of `job`, or nothing is returned.
+	pub fn join(&self, job: &JobFile, privates: &[PrivateKey], random_view: bool) -> Result<(String, String, String), Error> {
+		self.verify()?;
+		if keys(&job.publics) != keys(&self.publics) {
+			return Err(Error::Mismatch("it was calculated for other public split key parts"));
+		}
+		if self.network != job.network {
+			return Err(Error::Mismatch("it is for another network"));
+		}
+		let mut missing = self.publics.clone();
+		for (i, private) in privates.iter().enumerate() {
+			let public = PublicKey::from_private_key(private).to_string();
+			match missing.iter().position(|p| *p == public) {
+				Some(p) => { missing.swap_remove(p); },
+				None => return Err(Error::Public(i)),
+			}
+		}
+		if let Some(public) = missing.pop() {
+			return Err(Error::Missing(public));
+		}
+
+		let part = PrivateKey::from_str(&self.part).map_err(|_| Error::Format("the part is not a valid scalar"))?;
+		let keys: Vec<PrivateKey> = privates.iter().copied().chain([part]).collect();
+		let m = crate::address::join_split_key(&keys, random_view, self.network);
+		let pattern = job.regex();
+		let regex = regex::Regex::new(&pattern).map_err(|_| Error::Format("the job's pattern is not a valid regex"))?;
+		if !m.0.starts_with(&self.address) || !regex.is_match(&m.0) {
+			return Err(Error::Pattern(pattern));
+		}
+		Ok(m)
+	}
+}
+
+// A hex public key as a point.
+fn point(hex: &str) -> Option<EdwardsPoint> {
+	PublicKey::from_str(hex).ok().and_then(|key| key.point.decompress())
+}
+
+// The first 44 characters of the address of `spend`.
+fn address(network: Network, spend: &EdwardsPoint) -> String {
+	let mut bytes = [0_u8; 33];
+	bytes[0] = network.byte();
+	bytes[1..].copy_from_slice(spend.compress().as_bytes());
+	let mut addr = [0_u8; SPEND_CHARS];
+	let len = crate::encode::encode_blocks(&bytes, SPEND_CHARS / crate::encode::CHUNK, &mut addr);
+	String::from_utf8_lossy(&addr[..len]).into_owned()
+}
+
+//---------------------------------------------------------------------------------------------------- Files
+/// A split key search to hand out, written by `split-job`.
+///
+/// `--calculate-split-key` takes it instead of the public parts + pattern.
+#[derive(Clone,Debug,PartialEq,Eq,Serialize,Deserialize)]
+pub struct JobFile {
+	pub version: u32,
+	pub network: Network,
+	/// The public split key part of every contributor.
+	pub publics: Vec<String>,
+	/// The pattern as entered.
+	pub pattern: String,
+	pub pattern_type: PatternType,
+	/// How many addresses to find.
+	pub count: u64,
+	/// Whether the joined wallets get a random private view key (`--random-view-key`).
+	pub random_view: bool,
+	/// How to reach the requester (free text).
+	pub contact: String,
+	/// The first 4 bytes of `Keccak256` of the file with an empty checksum.
+	pub checksum: String,
+}
+
+impl JobFile {
+	/// A new job, `publics` must already be validated.
+	pub fn new(publics: Vec<String>, pattern: String, pattern_type: PatternType, count: u64, network: Network, contact: String, random_view: bool) -> Self {
+		let mut job = Self { version: FILE_VERSION, network, publics, pattern, pattern_type, count, random_view, contact, checksum: String::new() };
+		job.checksum = checksum(&job);
+		job
+	}
+
+	/// The full regex that is matched.
+	pub fn regex(&self) -> String {
+		match self.pattern_type {
+			PatternType::Third => format!("^..{}.*$", self.pattern),
+			PatternType::First => self.pattern.clone(),
+		}
+	}
+
+	/// Read a job file, checking its version, checksum and public parts.
+	pub fn read(path: &Path) -> Result<Self, Error> {
+		let job: Self = read(path)?;
+		if job.checksum != checksum(&job) {
+			return Err(Error::Checksum);
+		}
+		publics(&job.publics)?;
+		Ok(job)
+	}
+
+	pub fn write(&self, path: &Path) -> Result<(), Error> {
+		write(path, self)
+	}
+
+	/// Is `proof` said to be for this job (its public parts, network and pattern)?
+	///
+	/// What a proof says is not trusted, [`Proof::join()`] still checks the joined address.
+	pub fn check(&self, proof: &Proof) -> Result<(), Error> {
+		if keys(&proof.publics) != keys(&self.publics) {
+			return Err(Error::Mismatch("it was calculated for other public split key parts"));
+		}
+		if proof.network != self.network {
+			return Err(Error::Mismatch("it is for another network"));
+		}
+		if proof.pattern != self.regex() {
+			return Err(Error::Mismatch("it matches another pattern"));
+		}
+		Ok(())
+	}
+}
+
+/// The calculated parts of a [`JobFile`], `--join-split-key` takes it instead of a `vanity...` blob.
+#[derive(Clone,Debug,PartialEq,Eq,Serialize,Deserialize)]
+pub struct ResultFile {
+	pub version: u32,
+	/// The job it was calculated for.
+	pub job: JobFile,
+	/// Every calculated part found.
+	pub results: Vec<Proof>,
+	/// The first 4 bytes of `Keccak256` of the file with an empty checksum.
+	pub checksum: String,
+}
+
+impl ResultFile {
+	/// The result file at `path`, its results are kept if it is of the same `job`.
+	pub fn open(path: &Path, job: &JobFile) -> Result<Self, Error> {
+		if path.exists() {
+			let file = Self::read(path)?;
+			return match file.job == *job {
+				true  => Ok(file),
+				false => Err(Error::File(format!("Result file [{}] already exists for another job", path.display()))),
+			};
+		}
+		let mut file = Self { version: FILE_VERSION, job: job.clone(), results: Vec::new(), checksum: String::new() };
+		file.checksum = checksum(&file);
+		Ok(file)
+	}
+
+	/// Add a calculated part and re-write the file.
+	pub fn add(&mut self, proof: Proof, path: &Path) -> Result<(), Error> {
+		self.results.push(proof);
+		self.checksum = checksum(self);
+		write(path, self)
+	}
+
+	/// Read a result file, checking its version, checksum and that every result is for its job.
+	pub fn read(path: &Path) -> Result<Self, Error> {
+		let file: Self = read(path)?;
+		if file.checksum != checksum(&file) || file.job.checksum != checksum(&file.job) {
+			return Err(Error::Checksum);
+		}
+		for proof in &file.results {
+			file.job.check(proof)?;
+		}
+		Ok(file)
+	}
+}
+
+/// Where the results of the job file at `job` are written, `hinto.json` -> `hinto.result.json`.
+pub fn result_path(job: &Path) -> PathBuf {
+	let stem = job.file_stem().unwrap_or_default().to_string_lossy();
+	job.with_file_name(format!("{stem}.result.json"))
+}
+
+/// Is this meant as a hex public split key part (instead of a job file)?
+///
+/// Any length counts, so a bare key without its proof of possession is refused by [`public()`].
+pub fn is_key(s: &str) -> bool {
+	!s.is_empty() && s.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+// The checksum of `file` (or proof) with its own checksum empty.
+fn checksum<T: Serialize>(file: &T) -> String {
+	let mut value = serde_json::to_value(file).expect("files always serialize");
+	value["checksum"] = serde_json::Value::String(String::new());
+	let json = serde_json::to_vec(&value).expect("files always serialize");
+	monero::cryptonote::hash::keccak_256(&json)[..4].iter().map(|b| format!("{b:02x}")).collect()
+}
+
+// Read a JSON file of the current version.
+fn read<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T, Error> {
+	let text = std::fs::read_to_string(path).map_err(|e| Error::File(format!("Could not read [{}]: {e}", path.display())))?;
+	let value: serde_json::Value = serde_json::from_str(&text).map_err(|e| Error::File(format!("[{}] is not JSON: {e}", path.display())))?;
+	match value.get("version").and_then(serde_json::Value::as_u64) {
+		Some(v) if v == FILE_VERSION as u64 => (),
+		Some(v) => return Err(Error::Version(v as u32)),
+		None => return Err(Error::File(format!("[{}] has no version", path.display()))),
+	}
+	serde_json::from_value(value).map_err(|e| Error::File(format!("[{}] is missing fields: {e}", path.display())))
+}
+
+// Write a pretty JSON file.
+fn write<T: Serialize>(path: &Path, file: &T) -> Result<(), Error> {
+	let json = serde_json::to_string_pretty(file).expect("files always serialize");
+	crate::vault::write_atomic(path, json.as_bytes()).map_err(|e| Error::File(format!("Could not write [{}]: {e}", path.display())))
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// The public key of `private`.
+	fn public_key(private: &PrivateKey) -> EdwardsPoint {
+		&private.scalar * &ED25519_BASEPOINT_TABLE
+	}
+
+	// `n` contributor keys + a proof of a part calculated for them.
+	fn proof(n: usize) -> (Vec<PrivateKey>, Proof) {
+		let privates: Vec<PrivateKey> = (0..n).map(|_| crate::address::rand_priv()).collect();
+		let publics: Vec<EdwardsPoint> = privates.iter().map(public_key).collect();
+		let part = crate::address::rand_priv();
+		(privates, Proof::new(&part, &publics, "^5.*$", 1000, Network::Stagenet))
+	}
+
+	// The requester's job of `proof`, looking for `pattern`.
+	fn job(proof: &Proof, pattern: &str) -> JobFile {
+		JobFile::new(proof.publics.clone(), pattern.into(), PatternType::First, 1, proof.network, String::new(), false)
+	}
+
+	#[test]
+	fn join() {
+		for n in [1, 2, 5] {
+			let (mut privates, proof) = proof(n);
+			let proof = Proof::from_blob(&proof.to_blob()).unwrap();
+			privates.reverse();
+			let m = proof.join(&job(&proof, "^5.*$"), &privates, false).unwrap();
+			assert!(m.0.starts_with(&proof.address));
+			assert_eq!(monero::Address::from_str(&m.0).unwrap().public_spend.to_string(), proof.spend);
+		}
+	}
+
+	// Small order points that are rejected by Monero too.
+	const SMALL_ORDER: [&str; 6] = [
+		"0000000000000000000000000000000000000000000000000000000000000000",
+		"0000000000000000000000000000000000000000000000000000000000000080",
+		"26e8958fc2b227b045c3f489f2ef98f0d5dfac05d3c63339b13802886d53fc05",
+		"c7176a703d4dd84fba3c0b760d10670f2a2053fa2c39ccc64ec7fd7792ac037a",
+		"ecffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+		"26e8958fc2b227b045c3f489f2ef98f0d5dfac05d3c63339b13802886d53fc85",
+	];
+
+	#[test]
+	fn bad_publics() {
+		let private = crate::address::rand_priv();
+		let (key, hex) = (public_key(&private), part(&private));
+		assert_eq!(hex.len(), PART_LEN);
+		assert!(is_key(&hex));
+
+		assert_eq!(publics(&[&hex]), Ok(vec![key]));
+		let again = part(&private);
+		assert_eq!(publics(&[hex.clone(), again.clone()]), Err(Error::Duplicate(again)));
+		assert_eq!(publics(&[&hex, &part(&PrivateKey { scalar: -private.scalar })]), Err(Error::Cancelled));
+		for bad in ["hinto", "00"] {
+			assert_eq!(publics(&[bad]), Err(Error::Invalid(bad.into())));
+		}
+		let bad = "x".repeat(PART_LEN);
+		assert_eq!(publics(&[&bad]), Err(Error::Invalid(bad[..64].into())));
+	}
+
+	// The README's public split key part.
+	const VECTOR: &str = "219126f1bd60e5c0593b3fd80b7493ad71bbb7cd48692e8fb21ab9357294ad44b422e6f201392984380cdae30044bbb45fa1a5590263bcd7fdf1b134a9019de9756f6e49882f5d055181cdc2a8c2e1040b60e5039119907066644b28b8e00f00";
+
+	#[test]
+	fn possession() {
+		let private = crate::address::rand_priv();
+		let hex = part(&private);
+		let key = &hex[..64];
+
+		// A proof made outside of this crate.
+		let readme = PrivateKey::from_str("f2149a0c0d09504b71e54cb91358f9f38d10e41d61dea5049dc835dc84204403").unwrap();
+		assert_eq!(public(VECTOR), Ok(public_key(&readme)));
+
+		// A bare key, or someone else's proof.
+		assert_eq!(public(key), Err(Error::Possession(key.into())));
+		let other = part(&crate::address::rand_priv());
+		assert_eq!(public(&format!("{key}{}", &other[64..])), Err(Error::Possession(key.into())));
+
+		// A changed `R` or `s`.
+		let s = PrivateKey::from_str(&hex[128..]).unwrap();
+		let changed = format!("{}{}", &hex[..128], PrivateKey { scalar: s.scalar + Scalar::one() });
+		assert_eq!(public(&changed), Err(Error::Possession(key.into())));
+		let changed = format!("{key}{}{}", &other[64..128], &hex[128..]);
+		assert_eq!(public(&changed), Err(Error::Possession(key.into())));
+		assert_eq!(public(&format!("{hex}00")), Err(Error::Possession(key.into())));
+
+		// `X - sum(others)` has no private part anyone knows, so no proof.
+		let rogue = PublicKey { point: (public_key(&crate::address::rand_priv()) - public_key(&private)).compress() }.to_string();
+		assert_eq!(publics(&[hex.clone(), rogue.clone()]), Err(Error::Possession(rogue)));
+	}
+
+	#[test]
+	fn weak_publics() {
+		let identity = "0100000000000000000000000000000000000000000000000000000000000000";
+		assert_eq!(key(identity), Err(Error::Identity(identity.into())));
+		let with_proof = format!("{identity}{}", &part(&crate::address::rand_priv())[64..]);
+		assert_eq!(public(&with_proof), Err(Error::Identity(identity.into())));
+
+		// `y = p + 1` is the identity, encoded non-canonically.
+		let non_canonical = "eeffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f";
+		assert_eq!(key(non_canonical), Err(Error::NonCanonical(non_canonical.into())));
+
+		for small in SMALL_ORDER {
+			assert_eq!(key(small), Err(Error::SmallOrder(small.into())), "{small}");
+		}
+
+		// Every torsion point (and a real key + any of them).
+		let key = public_key(&crate::address::rand_priv());
+		for (i, torsion) in curve25519_dalek::constants::EIGHT_TORSION.iter().enumerate() {
+			let hex = PublicKey { point: torsion.compress() }.to_string();
+			match i {
+				0 => assert_eq!(self::key(&hex), Err(Error::Identity(hex.clone()))),
+				_ => assert_eq!(self::key(&hex), Err(Error::SmallOrder(hex.clone()))),
+			}
+			let mixed = PublicKey { point: (key + torsion).compress() }.to_string();
+			match i {
+				0 => assert_eq!(self::key(&mixed), Ok(key)),
+				_ => assert_eq!(self::key(&mixed), Err(Error::Torsion(mixed.clone()))),
+			}
+		}
+	}
+
+	#[test]
+	fn refuse() {
+		let (privates, proof) = proof(3);
+		let private = privates[0];
+		let job = job(&proof, "^5.*$");
+
+		// Someone else's part, or a missing one.
+		let mut wrong = privates.clone();
+		wrong[1] = crate::address::rand_priv();
+		assert_eq!(proof.join(&job, &wrong, false), Err(Error::Public(1)));
+		assert_eq!(proof.join(&job, &privates[..2], false), Err(Error::Missing(proof.publics[2].clone())));
+		assert_eq!(proof.join(&job, &[privates[0], privates[0], privates[1]], false), Err(Error::Public(1)));
+
+		// Any changed field is caught by the checksum.
+		let mut changed = proof.clone();
+		changed.tries += 1;
+		assert_eq!(changed.join(&job, &privates, false), Err(Error::Corrupted));
+
+		// But the checksum is no signature, the helper's claimed pattern is never trusted.
+		let mut changed = proof.clone();
+		changed.pattern = "^5hintohinto.*$".into();
+		changed.checksum = checksum(&changed);
+		assert!(changed.join(&job, &privates, false).is_ok());
+		let hinto = JobFile { pattern: "^5hintohinto.*$".into(), ..job.clone() };
+		assert_eq!(changed.join(&hinto, &privates, false), Err(Error::Pattern("^5hintohinto.*$".into())));
+
+		// A part for another job.
+		let other = JobFile { publics: proof.publics[..2].to_vec(), ..job.clone() };
+		assert_eq!(proof.join(&other, &privates, false), Err(Error::Mismatch("it was calculated for other public split key parts")));
+		let other = JobFile { network: Network::Mainnet, ..job.clone() };
+		assert_eq!(proof.join(&other, &privates, false), Err(Error::Mismatch("it is for another network")));
+
+		// Parts typed in uppercase are still the same parts.
+		let upper = JobFile::new(proof.publics.iter().map(|p| p.to_uppercase()).collect(), "^5.*$".into(), PatternType::First, 1, proof.network, String::new(), false);
+		assert!(proof.join(&upper, &privates, false).is_ok());
+		assert!(upper.check(&proof).is_ok());
+
+		// A part that does not match the requester's pattern.
+		let wrong = Proof::new(&crate::address::rand_priv(), &[public_key(&private)], "^5.*$", 1, Network::Stagenet);
+		let hinto = JobFile { publics: wrong.publics.clone(), ..hinto };
+		assert_eq!(wrong.join(&hinto, &[private], false), Err(Error::Pattern("^5hintohinto.*$".into())));
+
+		assert!(matches!(Proof::from_blob("hinto"), Err(Error::Format(_))));
+		assert!(matches!(Proof::from_blob("vanity0OIl"), Err(Error::Format(_))));
+	}
+	#[test]
+	fn files() {
+		let dir = std::env::temp_dir().join(format!("monero-vanity-split-{}", std::process::id()));
+		std::fs::create_dir_all(&dir).unwrap();
+		let (privates, proof) = proof(2);
+		let parts: Vec<String> = privates.iter().map(part).collect();
+		let job = JobFile::new(parts.clone(), "^5.*$".into(), PatternType::First, 2, Network::Stagenet, "hinto@example.com".into(), false);
+		assert_eq!(job.regex(), proof.pattern);
+
+		// Job file round-trip.
+		let path = dir.join("hinto.json");
+		job.write(&path).unwrap();
+		assert_eq!(JobFile::read(&path).unwrap(), job);
+		assert_eq!(result_path(&path), dir.join("hinto.result.json"));
+
+		// Results are kept when re-opened for the same job.
+		let result = result_path(&path);
+		let mut file = ResultFile::open(&result, &job).unwrap();
+		file.add(proof.clone(), &result).unwrap();
+		let file = ResultFile::open(&result, &job).unwrap();
+		assert_eq!(file.results, [proof.clone()]);
+		assert!(file.results[0].join(&job, &privates, false).is_ok());
+
+		// But not for another job.
+		let other = JobFile::new(parts, "^5a.*$".into(), PatternType::First, 2, Network::Stagenet, String::new(), false);
+		assert!(matches!(ResultFile::open(&result, &other), Err(Error::File(_))));
+
+		// Any change is caught by the checksum.
+		let text = std::fs::read_to_string(&path).unwrap();
+		std::fs::write(&path, text.replace("\"count\": 2", "\"count\": 3")).unwrap();
+		assert_eq!(JobFile::read(&path), Err(Error::Checksum));
+		std::fs::write(&path, text.replace("\"version\": 1", "\"version\": 2")).unwrap();
+		assert_eq!(JobFile::read(&path), Err(Error::Version(2)));
+
+		// A result for another pattern (with a valid checksum).
+		let mut file = ResultFile::read(&result).unwrap();
+		file.job = other;
+		file.checksum = checksum(&file);
+		write(&result, &file).unwrap();
+		assert_eq!(ResultFile::read(&result), Err(Error::Mismatch("it matches another pattern")));
+
+		std::fs::remove_dir_all(&dir).unwrap();
+	}
+}
@@ -1,5 +1,5 @@
 //---------------------------------------------------------------------------------------------------- Use
-use regex::Regex;
+use crate::regexes::Patterns;
 use std::sync::atomic::{AtomicBool, AtomicU64};
 use std::sync::Arc;
 use std::time::Instant;
@@ -10,12 +10,48 @@ pub struct State {
 	// Set-up variables.
 	/// How many threads to use.
 	pub threads: usize,
-	/// The address regex pattern to look for.
-	pub pattern: Regex,
+	/// The address regex pattern(s) to look for.
+	pub pattern: Patterns,
 	/// The address regex pattern to look for (as a String).
 	pub pattern_string: String,
-	/// The optional public key part of a split key we are calculating
-	pub split_key: Option<curve25519_dalek::edwards::EdwardsPoint>,
+	/// The optional public key parts (of every contributor) of a split key we are calculating
+	pub split_key: Option<Vec<curve25519_dalek::edwards::EdwardsPoint>>,
+	/// Should the private view key be random instead of derived from the spend key?
+	pub random_view: bool,
+	/// Which Monero network are we generating addresses for?
+	pub network: crate::network::Network,
+	/// The optional private view key + public spend key of the wallet we are finding a subaddress for.
+	pub subaddress: Option<(monero::PrivateKey, curve25519_dalek::edwards::EdwardsPoint)>,
+	/// The subaddress account (major index).
+	pub major: u32,
+	/// Search random subaddress indices instead of walking from the start?
+	pub random_index: bool,
+	/// The optional public spend + view key of the wallet we are finding an integrated address for.
+	pub integrated: Option<(monero::PublicKey, monero::PublicKey)>,
+	/// The optional private spend key that stays fixed while searching view keys for a suffix.
+	pub suffix: Option<monero::PrivateKey>,
+	/// The optional suffix regex (+ String) to search for after the prefix is found.
+	pub suffix_pattern: Option<(Patterns, String)>,
+	/// The phase 1 (prefix) spend key, tries and time of a prefix + suffix search.
+	pub phase_one: Option<(monero::PrivateKey, u64, std::time::Duration)>,
+	/// Which patterns stop after their first hit (by index, empty if none).
+	pub once: Vec<bool>,
+	/// How many addresses to find before stopping (`None` is infinite).
+	pub count: Option<u64>,
+	/// Are the patterns expanded to match every base58 case?
+	pub ignore_case: bool,
+	/// Are the patterns expanded with leetspeak/lookalike substitutes?
+	pub leet: bool,
+	/// How many times more likely a match is with `ignore_case`/`leet` (if known).
+	pub likelier: Option<f64>,
+	/// The chance a single try matches (if known).
+	pub chance: Option<crate::difficulty::Chance>,
+	/// Where the pattern is matched in an integrated address/suffix.
+	pub tail_type: crate::pattern::TailType,
+	/// Where every worker is, for checkpoints.
+	pub positions: crate::checkpoint::Positions,
+	/// How the CLI prints.
+	pub output: crate::output::Output,
 	/// How many iterations are we on?
 	pub iter: Arc<AtomicU64>,
 
@@ -28,6 +64,8 @@ pub struct State {
 	pub start: Instant,
 	/// How many seconds since starting?
 	pub elapsed: readable::Time,
+	/// How many addresses were found this run?
+	pub found: u64,
 	/// Found Private Spend Key(s).
 	pub history: String,
 
@@ -40,14 +78,33 @@ impl Default for State {
 	fn default() -> Self {
 		Self {
 			threads: 1,
-			pattern: Regex::new("").unwrap(),
+			pattern: Patterns::default(),
 			pattern_string: "".to_string(),
 			split_key: None,
+			random_view: false,
+			network: crate::network::Network::default(),
+			subaddress: None,
+			major: 0,
+			random_index: false,
+			integrated: None,
+			suffix: None,
+			suffix_pattern: None,
+			phase_one: None,
+			once: Vec::new(),
+			count: Some(1),
+			ignore_case: false,
+			leet: false,
+			likelier: None,
+			chance: None,
+			tail_type: crate::pattern::TailType::default(),
+			positions: crate::checkpoint::positions(1, None),
+			output: crate::output::Output::default(),
 			iter: Arc::new(AtomicU64::new(0)),
 			iterating: false,
 			speed: 0,
 			start: Instant::now(),
 			elapsed: readable::Time::from(0_u8),
+			found: 0,
 			history: "".to_string(),
 			die: Arc::new(AtomicBool::new(false)),
 		}
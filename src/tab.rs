@@ -0,0 +1,29 @@
+//---------------------------------------------------------------------------------------------------- Use
+
+//---------------------------------------------------------------------------------------------------- Tab
+/// Which tab of the `GUI` is shown.
+#[derive(Clone,Copy,Debug,Default,PartialEq,Eq)]
+pub enum Tab {
+	/// Search for a new wallet/integrated address.
+	#[default]
+	Search,
+	/// Generate, search for and join split keys.
+	SplitKey,
+}
+
+impl std::fmt::Display for Tab {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Search   => write!(f, "Search"),
+			Self::SplitKey => write!(f, "Split Key"),
+		}
+	}
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+//#[cfg(test)]
+//mod tests {
+//  #[test]
+//  fn __TEST__() {
+//  }
+//}
@@ -0,0 +1,199 @@
+//---------------------------------------------------------------------------------------------------- Use
+use argon2::Argon2;
+use chacha20poly1305::{
+	aead::{Aead,NewAead},
+	XChaCha20Poly1305,
+	XNonce,
+};
+use rand::Rng;
+
+//---------------------------------------------------------------------------------------------------- Constants
+/// The start of every encrypted file.
+const MAGIC: &[u8; 8] = b"MVVAULT1";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+const HEADER_LEN: usize = MAGIC.len() + SALT_LEN + NONCE_LEN;
+
+//---------------------------------------------------------------------------------------------------- Vault
+/// A password-encrypted envelope.
+///
+/// The key is derived once with `Argon2id` (random salt),
+/// every [`Vault::seal()`] uses a new random `XChaCha20-Poly1305` nonce.
+/// ```text
+/// MVVAULT1 || salt (16) || nonce (24) || ciphertext + tag
+/// ```
+#[derive(Clone)]
+pub struct Vault {
+	salt: [u8; SALT_LEN],
+	cipher: XChaCha20Poly1305,
+}
+
+impl Vault {
+	/// A new vault with a random salt.
+	pub fn new(password: &str) -> Result<Self, &'static str> {
+		let salt = rand::thread_rng().gen();
+		Self::with_salt(password, salt)
+	}
+
+	fn with_salt(password: &str, salt: [u8; SALT_LEN]) -> Result<Self, &'static str> {
+		if password.is_empty() {
+			return Err("Password must not be empty");
+		}
+		let mut key = [0_u8; 32];
+		if Argon2::default().hash_password_into(password.as_bytes(), &salt, &mut key).is_err() {
+			return Err("Password key derivation failed");
+		}
+		Ok(Self { salt, cipher: XChaCha20Poly1305::new(&key.into()) })
+	}
+
+	/// Encrypt `plaintext` into the file format.
+	pub fn seal(&self, plaintext: &[u8]) -> Vec<u8> {
+		let nonce: [u8; NONCE_LEN] = rand::thread_rng().gen();
+		let ciphertext = self.cipher
+			.encrypt(XNonce::from_slice(&nonce), plaintext)
+			.expect("encrypting into a Vec never fails");
+
+		let mut out = Vec::with_capacity(HEADER_LEN + ciphertext.len());
+		out.extend_from_slice(MAGIC);
+		out.extend_from_slice(&self.salt);
+		out.extend_from_slice(&nonce);
+		out.extend_from_slice(&ciphertext);
+		out
+	}
+
+	/// Decrypt a sealed file, also returning the
+	/// vault so it can be re-sealed with the same key.
+	pub fn open(password: &str, data: &[u8]) -> Result<(Self, Vec<u8>), &'static str> {
+		if data.len() < HEADER_LEN || &data[..MAGIC.len()] != MAGIC {
+			return Err("File is not an encrypted monero-vanity file");
+		}
+		let (salt, rest) = data[MAGIC.len()..].split_at(SALT_LEN);
+		let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+
+		let vault = Self::with_salt(password, salt.try_into().expect("split at SALT_LEN"))?;
+		match vault.cipher.decrypt(XNonce::from_slice(nonce), ciphertext) {
+			Ok(plaintext) => Ok((vault, plaintext)),
+			Err(_) => Err("Wrong password or corrupted file"),
+		}
+	}
+}
+
+//---------------------------------------------------------------------------------------------------- Password
+/// What a password is for, each has its own environment variable
+/// so a script never hands 1 secret to every file.
+#[derive(Copy,Clone,Debug,PartialEq,Eq)]
+pub enum Purpose {
+	/// `--checkpoint`/`--resume` files.
+	Checkpoint,
+	/// `--encrypt` results files.
+	Results,
+	/// `--wallet-dir` wallets.
+	Wallet,
+}
+
+impl Purpose {
+	/// Where the password is read from before asking for it.
+	pub const fn env(self) -> &'static str {
+		match self {
+			Self::Checkpoint => "MONERO_VANITY_CHECKPOINT_PASSWORD",
+			Self::Results    => "MONERO_VANITY_RESULTS_PASSWORD",
+			Self::Wallet     => "MONERO_VANITY_WALLET_PASSWORD",
+		}
+	}
+
+	const fn name(self) -> &'static str {
+		match self {
+			Self::Checkpoint => "Checkpoint",
+			Self::Results    => "Results",
+			Self::Wallet     => "Wallet",
+		}
+	}
+}
+
+/// The password of an existing file from its [`Purpose::env()`], or ask for it.
+pub fn password(purpose: Purpose) -> Result<String, &'static str> {
+	match std::env::var(purpose.env()) {
+		Ok(password) => Ok(password),
+		Err(_) => ask(&format!("{} password: ", purpose.name())),
+	}
+}
+
+/// A password for a new file from its [`Purpose::env()`], or ask for it twice.
+///
+/// Empty passwords are rejected.
+pub fn new_password(purpose: Purpose) -> Result<String, &'static str> {
+	let password = match std::env::var(purpose.env()) {
+		Ok(password) => password,
+		Err(_) => {
+			let password = ask(&format!("New {} password: ", purpose.name().to_lowercase()))?;
+			if !password.is_empty() && ask("Confirm password: ")? != password {
+				return Err("Passwords don't match");
+			}
+			password
+		},
+	};
+	match password.is_empty() {
+		true  => Err("Password must not be empty"),
+		false => Ok(password),
+	}
+}
+
+/// Ask for a password on the terminal.
+pub fn ask(prompt: &str) -> Result<String, &'static str> {
+	rpassword::prompt_password(prompt).map_err(|_| "Could not read the password from the terminal")
+}
+
+/// Write `data` to `path` by writing a temporary file and renaming it,
+/// so a crash never leaves a half written file behind.
+pub fn write_atomic(path: &std::path::Path, data: &[u8]) -> std::io::Result<()> {
+	let mut tmp = path.as_os_str().to_owned();
+	tmp.push(".tmp");
+	std::fs::write(&tmp, data)?;
+	std::fs::rename(&tmp, path)
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn seal_and_open() {
+		let vault = Vault::new("hunter2").unwrap();
+		let sealed = vault.seal(b"secret");
+		assert!(sealed.starts_with(MAGIC));
+		assert!(!sealed.windows(6).any(|w| w == b"secret"));
+
+		let (vault, plaintext) = Vault::open("hunter2", &sealed).unwrap();
+		assert_eq!(plaintext, b"secret");
+
+		// Re-sealing keeps the salt but not the nonce.
+		let resealed = vault.seal(b"secret");
+		assert_eq!(resealed[..MAGIC.len() + SALT_LEN], sealed[..MAGIC.len() + SALT_LEN]);
+		assert_ne!(resealed, sealed);
+	}
+
+	#[test]
+	fn wrong_password() {
+		let sealed = Vault::new("hunter2").unwrap().seal(b"secret");
+		assert!(Vault::open("hunter3", &sealed).is_err());
+		assert!(Vault::open("hunter2", &sealed[..20]).is_err());
+		assert!(Vault::new("").is_err());
+	}
+
+	#[test]
+	// Each file has its own variable, new passwords can't be empty.
+	fn passwords_from_env() {
+		let purposes = [Purpose::Checkpoint, Purpose::Results, Purpose::Wallet];
+		for (i, purpose) in purposes.iter().enumerate() {
+			assert!(purposes[i + 1..].iter().all(|p| p.env() != purpose.env()));
+		}
+
+		std::env::set_var(Purpose::Checkpoint.env(), "hunter2");
+		assert_eq!(password(Purpose::Checkpoint).unwrap(), "hunter2");
+		assert_eq!(new_password(Purpose::Checkpoint).unwrap(), "hunter2");
+		std::env::set_var(Purpose::Checkpoint.env(), "");
+		assert!(new_password(Purpose::Checkpoint).is_err());
+		std::env::remove_var(Purpose::Checkpoint.env());
+	}
+}
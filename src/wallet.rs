@@ -0,0 +1,306 @@
+//---------------------------------------------------------------------------------------------------- Use
+use crate::network::Network;
+use chacha20::{
+	cipher::{NewCipher,StreamCipher},
+	ChaCha20Legacy,
+	Key,
+	LegacyNonce,
+};
+use monero::{PrivateKey,PublicKey};
+use rand::Rng;
+use std::io::Write;
+use std::path::{Path,PathBuf};
+
+//---------------------------------------------------------------------------------------------------- Constants
+/// `epee` portable storage header (signature A, signature B, version).
+const EPEE_HEADER: [u8; 9] = [0x01, 0x11, 0x01, 0x01, 0x01, 0x01, 0x02, 0x01, 0x01];
+const EPEE_UINT64: u8 = 5;
+const EPEE_STRING: u8 = 10;
+const EPEE_OBJECT: u8 = 12;
+const IV_LEN: usize = 8;
+
+//---------------------------------------------------------------------------------------------------- Wallet
+/// A `monero-wallet-cli` compatible `.keys` file.
+///
+/// This is what `wallet2` writes (without the cache file):
+/// ```text
+/// iv (8) || varint(len) || ChaCha20(key = cn_slow_hash(password), iv, json)
+/// ```
+/// The JSON holds the `epee` serialized account in `key_data`,
+/// secret keys are not encrypted a 2nd time (`encrypted_secret_keys: 0`).
+///
+/// `key` is `cn_slow_hash(password)`, the same for every wallet with that password.
+pub fn keys_file(spend: &PrivateKey, view: &PrivateKey, network: Network, key: &[u8; 32]) -> Vec<u8> {
+	let mut data = json(spend, view, network);
+
+	let iv: [u8; IV_LEN] = rand::thread_rng().gen();
+	ChaCha20Legacy::new(Key::from_slice(key), LegacyNonce::from_slice(&iv)).apply_keystream(&mut data);
+
+	let mut file = iv.to_vec();
+	varint(&mut file, data.len() as u64);
+	file.extend_from_slice(&data);
+	file
+}
+
+/// Where found wallets are written (`--wallet-dir`).
+pub struct WalletDir {
+	dir: PathBuf,
+	key: [u8; 32],
+}
+
+impl WalletDir {
+	/// Wallets in `dir` (created if needed), all with the same `password`.
+	///
+	/// The password is hashed once here (~1 second),
+	/// so writing a found wallet doesn't hold up the search.
+	pub fn new(dir: PathBuf, password: &str) -> Self {
+		Self { dir, key: crate::cryptonight::slow_hash(password.as_bytes()) }
+	}
+
+	/// Where the wallets are written.
+	pub fn path(&self) -> &Path {
+		&self.dir
+	}
+
+	/// Write the `.keys` file of a found address.
+	///
+	/// The wallet is named after the start of the address, existing files are never
+	/// overwritten. This returns the name `monero-wallet-cli --wallet-file` expects.
+	pub fn write(&self, address: &str, spend: &PrivateKey, view: &PrivateKey, network: Network) -> Result<PathBuf, &'static str> {
+		let name = &address[..address.len().min(16)];
+		let file = keys_file(spend, view, network, &self.key);
+		std::fs::create_dir_all(&self.dir).map_err(|_| "Could not create the wallet directory")?;
+
+		// `create_new` refuses a file that appeared after checking the name.
+		let paths = (0..).map(|i| match i {
+			0 => self.dir.join(format!("{name}.keys")),
+			i => self.dir.join(format!("{name}-{i}.keys")),
+		});
+		for path in paths {
+			if path.with_extension("").exists() {
+				continue;
+			}
+			let mut keys = match std::fs::OpenOptions::new().write(true).create_new(true).open(&path) {
+				Ok(keys) => keys,
+				Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+				Err(_) => return Err("Could not create the wallet file"),
+			};
+			if keys.write_all(&file).and_then(|_| keys.sync_all()).is_err() {
+				let _ = std::fs::remove_file(&path);
+				return Err("Could not write the wallet file");
+			}
+			return Ok(path.with_extension(""));
+		}
+		unreachable!("there is always a free name")
+	}
+}
+
+// `wallet2::get_keys_file_data()`.
+fn json(spend: &PrivateKey, view: &PrivateKey, network: Network) -> Vec<u8> {
+	let mut json = b"{\"key_data\":\"".to_vec();
+	for &byte in &key_data(spend, view) {
+		// Like `rapidjson`, other bytes are written as-is.
+		match byte {
+			b'"' | b'\\' => json.extend_from_slice(&[b'\\', byte]),
+			0x00..=0x1f  => json.extend_from_slice(format!("\\u{byte:04x}").as_bytes()),
+			_            => json.push(byte),
+		}
+	}
+	json.extend_from_slice(b"\",");
+
+	// Only wallets with a seed have a seed language.
+	if crate::address::view_from_spend(spend) == *view {
+		json.extend_from_slice(b"\"seed_language\":\"English\",");
+	}
+	let nettype = match network {
+		Network::Mainnet  => 0,
+		Network::Testnet  => 1,
+		Network::Stagenet => 2,
+	};
+	json.extend_from_slice(format!(
+		"\"key_on_device\":0,\"watch_only\":0,\"multisig\":0,\"encrypted_secret_keys\":0,\"nettype\":{nettype}}}"
+	).as_bytes());
+	json
+}
+
+// `epee::serialization::store_t_to_binary(account_base)`.
+fn key_data(spend: &PrivateKey, view: &PrivateKey) -> Vec<u8> {
+	let timestamp = std::time::SystemTime::now()
+		.duration_since(std::time::UNIX_EPOCH)
+		.map_or(0, |d| d.as_secs());
+
+	let mut data = EPEE_HEADER.to_vec();
+	epee_varint(&mut data, 2);
+	entry(&mut data, "m_keys", EPEE_OBJECT);
+	epee_varint(&mut data, 3);
+	entry(&mut data, "m_account_address", EPEE_OBJECT);
+	epee_varint(&mut data, 2);
+	string(&mut data, "m_spend_public_key", PublicKey::from_private_key(spend).as_bytes());
+	string(&mut data, "m_view_public_key", PublicKey::from_private_key(view).as_bytes());
+	string(&mut data, "m_spend_secret_key", spend.as_bytes());
+	string(&mut data, "m_view_secret_key", view.as_bytes());
+	entry(&mut data, "m_creation_timestamp", EPEE_UINT64);
+	data.extend_from_slice(&timestamp.to_le_bytes());
+	data
+}
+
+fn entry(data: &mut Vec<u8>, name: &str, kind: u8) {
+	data.push(name.len() as u8);
+	data.extend_from_slice(name.as_bytes());
+	data.push(kind);
+}
+
+fn string(data: &mut Vec<u8>, name: &str, value: &[u8]) {
+	entry(data, name, EPEE_STRING);
+	epee_varint(data, value.len() as u64);
+	data.extend_from_slice(value);
+}
+
+// `epee` varint, the lowest 2 bits are the size (1/2/4/8 bytes).
+fn epee_varint(data: &mut Vec<u8>, value: u64) {
+	match value {
+		0..=0x3f            => data.push((value << 2) as u8),
+		0x40..=0x3fff       => data.extend_from_slice(&(((value << 2) | 1) as u16).to_le_bytes()),
+		0x4000..=0x3fffffff => data.extend_from_slice(&(((value << 2) | 2) as u32).to_le_bytes()),
+		_                   => data.extend_from_slice(&((value << 2) | 3).to_le_bytes()),
+	}
+}
+
+// The binary archive (LEB128) varint of the encrypted length.
+fn varint(data: &mut Vec<u8>, mut value: u64) {
+	while value >= 0x80 {
+		data.push((value as u8) | 0x80);
+		value >>= 7;
+	}
+	data.push(value as u8);
+}
+
+//---------------------------------------------------------------------------------------------------- TESTS
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use monero::{Address,KeyPair};
+	use std::collections::HashMap;
+
+	// Decrypt a `.keys` file like `wallet2::load_keys_buf()`.
+	fn decrypt(file: &[u8], password: &str) -> Vec<u8> {
+		let (iv, rest) = file.split_at(IV_LEN);
+		let (mut len, mut shift, mut read) = (0_u64, 0, 0);
+		for &byte in rest {
+			len |= ((byte & 0x7f) as u64) << shift;
+			shift += 7;
+			read += 1;
+			if byte < 0x80 {
+				break;
+			}
+		}
+		let mut data = rest[read..].to_vec();
+		assert_eq!(data.len() as u64, len);
+
+		let key = crate::cryptonight::slow_hash(password.as_bytes());
+		ChaCha20Legacy::new(Key::from_slice(&key), LegacyNonce::from_slice(iv)).apply_keystream(&mut data);
+		data
+	}
+
+	// The unescaped `key_data` string.
+	fn key_data(json: &[u8]) -> Vec<u8> {
+		let start = b"{\"key_data\":\"".len();
+		assert_eq!(&json[..start], b"{\"key_data\":\"");
+		let mut data = vec![];
+		let mut i = start;
+		loop {
+			match json[i] {
+				b'"'  => return data,
+				b'\\' if json[i + 1] == b'u' => {
+					data.push(u8::from_str_radix(std::str::from_utf8(&json[i + 2..i + 6]).unwrap(), 16).unwrap());
+					i += 6;
+				},
+				b'\\' => { data.push(json[i + 1]); i += 2; },
+				byte  => { data.push(byte); i += 1; },
+			}
+		}
+	}
+
+	fn read_epee_varint(data: &[u8], i: &mut usize) -> u64 {
+		let size = 1 << (data[*i] & 3);
+		let mut bytes = [0_u8; 8];
+		bytes[..size].copy_from_slice(&data[*i..*i + size]);
+		*i += size;
+		u64::from_le_bytes(bytes) >> 2
+	}
+
+	// Flatten an `epee` section into `path.to.name -> bytes`.
+	fn read_section(data: &[u8], i: &mut usize, prefix: &str, out: &mut HashMap<String, Vec<u8>>) {
+		for _ in 0..read_epee_varint(data, i) {
+			let len = data[*i] as usize;
+			let name = format!("{prefix}{}", std::str::from_utf8(&data[*i + 1..*i + 1 + len]).unwrap());
+			*i += 1 + len;
+			*i += 1;
+			match data[*i - 1] {
+				EPEE_OBJECT => read_section(data, i, &format!("{name}."), out),
+				EPEE_STRING => {
+					let len = read_epee_varint(data, i) as usize;
+					out.insert(name, data[*i..*i + len].to_vec());
+					*i += len;
+				},
+				EPEE_UINT64 => { out.insert(name, data[*i..*i + 8].to_vec()); *i += 8; },
+				kind => panic!("unexpected type {kind}"),
+			}
+		}
+	}
+
+	#[test]
+	fn keys_file_roundtrip() {
+		let spend = crate::address::rand_priv();
+		let view = crate::address::view_from_spend(&spend);
+		let expected = Address::from_keypair(monero::Network::Stagenet, &KeyPair { view, spend });
+
+		let file = keys_file(&spend, &view, Network::Stagenet, &crate::cryptonight::slow_hash(b"hunter2"));
+		let json = decrypt(&file, "hunter2");
+		assert!(json.ends_with(b"\"seed_language\":\"English\",\"key_on_device\":0,\"watch_only\":0,\"multisig\":0,\"encrypted_secret_keys\":0,\"nettype\":2}"));
+
+		let data = key_data(&json);
+		assert_eq!(data[..EPEE_HEADER.len()], EPEE_HEADER);
+		let mut fields = HashMap::new();
+		read_section(&data, &mut EPEE_HEADER.len(), "", &mut fields);
+		assert_eq!(fields.len(), 5);
+
+		let key = |name: &str| PrivateKey::from_slice(&fields[name]).unwrap();
+		let spend = key("m_keys.m_spend_secret_key");
+		let view = key("m_keys.m_view_secret_key");
+		assert_eq!(fields["m_keys.m_account_address.m_spend_public_key"], PublicKey::from_private_key(&spend).as_bytes());
+		assert_eq!(fields["m_keys.m_account_address.m_view_public_key"], PublicKey::from_private_key(&view).as_bytes());
+		assert_eq!(Address::from_keypair(monero::Network::Stagenet, &KeyPair { view, spend }), expected);
+	}
+
+	#[test]
+	fn wallet_dir_never_overwrites() {
+		let dir = std::env::temp_dir().join(format!("monero-vanity-wallets-{}", std::process::id()));
+		let wallets = WalletDir::new(dir.clone(), "hunter2");
+		let spend = crate::address::rand_priv();
+		let view = crate::address::view_from_spend(&spend);
+		let address = Address::from_keypair(monero::Network::Mainnet, &KeyPair { view, spend }).to_string();
+
+		let first = wallets.write(&address, &spend, &view, Network::Mainnet).unwrap();
+		let second = wallets.write(&address, &spend, &view, Network::Mainnet).unwrap();
+		let taken = dir.join(format!("{}-2.keys", &address[..16]));
+		std::fs::write(&taken, "hinto").unwrap();
+		let third = wallets.write(&address, &spend, &view, Network::Mainnet).unwrap();
+		let kept = std::fs::read_to_string(&taken).unwrap();
+		std::fs::remove_dir_all(&dir).unwrap();
+		assert_eq!(first, dir.join(&address[..16]));
+		assert_eq!(second, dir.join(format!("{}-1", &address[..16])));
+		assert_eq!(third, dir.join(format!("{}-3", &address[..16])));
+		assert_eq!(kept, "hinto");
+	}
+
+	#[test]
+	fn epee_varint_sizes() {
+		for (value, bytes) in [(2, vec![0x08]), (64, vec![0x01, 0x01]), (0x4000, vec![0x02, 0x00, 0x01, 0x00])] {
+			let mut data = vec![];
+			epee_varint(&mut data, value);
+			assert_eq!(data, bytes);
+			assert_eq!(read_epee_varint(&data, &mut 0), value);
+		}
+	}
+}